@@ -0,0 +1,336 @@
+#![deny(missing_docs)]
+//! Derive macros for `yew-custom-components`
+//!
+//! Enable them through the `derive` feature of `yew-custom-components`, which re-exports them
+//! next to the traits they implement.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use quote::format_ident;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Path};
+
+/// Derives `TableData` for a struct with named fields.
+///
+/// Every field is exposed under its name as a data property. Fields are rendered through
+/// `yew::html::ToHtml` and sorted by their `serde_value::Value` representation, unless told
+/// otherwise with the `#[table(...)]` field attribute:
+///
+/// * `skip` - The field is not exposed to the table.
+/// * `rename = "name"` - Data property under which the field is exposed.
+/// * `render = "path"` - Function `fn(&Self) -> Html` rendering the cell.
+/// * `sort_key = "path"` - Function `fn(&Self) -> serde_value::Value` giving the sorting key.
+/// * `searchable` - The field is matched against the search string, case insensitively,
+///   through its `Display` implementation. When no field is searchable, every row matches.
+/// * `key` - The field identifies the row, through its `Display` implementation.
+/// * `parent` - The field, an `Option`, holds the key of the parent row in a tree, through the
+///   `Display` implementation of its content.
+///
+/// The struct itself takes an optional `#[table(details = "path")]` attribute naming a function
+/// `fn(&Self) -> Option<Html>` that renders the detail row.
+///
+/// `TableFields` is implemented as well, with a generated `<Struct>Field` enum holding one
+/// variant per exposed field, named after the field in upper camel case. Columns reference
+/// these variants through `ColumnBuilder::field`, groupings through `TableFields::field_names`.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use yew::{html, Html};
+/// use yew_custom_components::table::types::{ColumnBuilder, TableData, TableField, TableFields};
+///
+/// #[derive(Clone, Default, PartialEq, PartialOrd, Serialize, TableData)]
+/// #[table(details = "render_details")]
+/// struct Line {
+///     #[table(key)]
+///     id: u32,
+///     #[table(searchable)]
+///     name: String,
+///     #[table(render = "render_amount")]
+///     amount: i64,
+///     #[table(parent)]
+///     parent: Option<u32>,
+///     #[table(skip)]
+///     internal: u8,
+/// }
+///
+/// fn render_amount(line: &Line) -> Html {
+///     html! { <b>{ line.amount }</b> }
+/// }
+///
+/// fn render_details(line: &Line) -> Option<Html> {
+///     (line.amount > 0).then(|| html! { <p>{ &line.name }</p> })
+/// }
+///
+/// let line = Line { id: 7, name: "Rent".to_string(), amount: 5, parent: Some(2), internal: 0 };
+/// assert!(line.matches_search(Some("rent".to_string())));
+/// assert_eq!(line.row_key(), Some("7".to_string()));
+/// assert_eq!(line.parent_key(), Some("2".to_string()));
+/// assert!(line.render_details().is_some());
+/// assert!(line.get_field_as_value("internal").is_err());
+///
+/// let column = ColumnBuilder::<Line>::new("Amount").field(LineField::Amount).build();
+/// assert_eq!(column.data_property.as_deref(), Some("amount"));
+/// assert_eq!(LineField::all().len(), 4);
+/// assert_eq!(Line::field_names(&[LineField::Parent, LineField::Name]), vec!["parent", "name"]);
+/// assert_eq!(LineField::from_name("amount"), Some(LineField::Amount));
+/// ```
+#[proc_macro_derive(TableData, attributes(table))]
+pub fn derive_table_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    table_data(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Options given to a field through the `#[table(...)]` attribute.
+struct FieldOptions {
+    ident: Ident,
+    variant: Ident,
+    name: String,
+    render: Option<Path>,
+    sort_key: Option<Path>,
+    searchable: bool,
+    key: bool,
+    parent: bool,
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<Option<FieldOptions>> {
+    let Some(ident) = field.ident.clone() else {
+        return Err(syn::Error::new_spanned(field, "TableData fields must be named"));
+    };
+    let mut options = FieldOptions {
+        name: ident.to_string(),
+        variant: variant(&ident),
+        ident,
+        render: None,
+        sort_key: None,
+        searchable: false,
+        key: false,
+        parent: false,
+    };
+    let mut skip = false;
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("table")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+            } else if meta.path.is_ident("searchable") {
+                options.searchable = true;
+            } else if meta.path.is_ident("key") {
+                options.key = true;
+            } else if meta.path.is_ident("parent") {
+                options.parent = true;
+            } else if meta.path.is_ident("rename") {
+                options.name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("render") {
+                options.render = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else if meta.path.is_ident("sort_key") {
+                options.sort_key = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported table attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok((!skip).then_some(options))
+}
+
+/// Returns the name of the variant of the field enum for a field, in upper camel case.
+fn variant(ident: &Ident) -> Ident {
+    let name: String = ident
+        .unraw()
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .concat();
+    format_ident!("{}", name, span = ident.span())
+}
+
+fn table_data(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "TableData can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "TableData can only be derived for structs with named fields",
+        ));
+    };
+    let fields = named
+        .named
+        .iter()
+        .map(field_options)
+        .collect::<syn::Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let mut details = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("table")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("details") {
+                details = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported table attribute"))
+            }
+        })?;
+    }
+    let render_details = details.map(|details| {
+        quote! {
+            fn render_details(&self) -> ::std::option::Option<::yew::Html> {
+                #details(self)
+            }
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let table = quote!(::yew_custom_components::table);
+    let value = quote!(#table::__private::serde_value::Value);
+
+    let html_arms = fields.iter().map(|f| {
+        let (ident, name) = (&f.ident, &f.name);
+        match &f.render {
+            Some(render) => quote!(#name => ::std::result::Result::Ok(#render(self)),),
+            None => quote!(#name => ::std::result::Result::Ok(::yew::html::ToHtml::to_html(&self.#ident)),),
+        }
+    });
+    let value_arms = fields.iter().map(|f| {
+        let (ident, name) = (&f.ident, &f.name);
+        match &f.sort_key {
+            Some(sort_key) => quote!(#name => ::std::result::Result::Ok(#sort_key(self)),),
+            None => quote! {
+                #name => #table::__private::serde_value::to_value(&self.#ident).map_err(|e| {
+                    #table::error::Error::NonSerializableField(::std::format!("{}: {}", field_name, e))
+                }),
+            },
+        }
+    });
+    let searchable = fields
+        .iter()
+        .filter(|f| f.searchable)
+        .map(|f| &f.ident)
+        .collect::<Vec<_>>();
+    let search = if searchable.is_empty() {
+        quote! {
+            fn matches_search(&self, _needle: ::std::option::Option<::std::string::String>) -> bool {
+                true
+            }
+        }
+    } else {
+        quote! {
+            fn matches_search(&self, needle: ::std::option::Option<::std::string::String>) -> bool {
+                match needle {
+                    ::std::option::Option::Some(needle) => {
+                        let needle = needle.to_lowercase();
+                        #(::std::string::ToString::to_string(&self.#searchable).to_lowercase().contains(&needle))||*
+                    }
+                    ::std::option::Option::None => true,
+                }
+            }
+        }
+    };
+    let keys = fields.iter().filter(|f| f.key).collect::<Vec<_>>();
+    if let Some(extra) = keys.get(1) {
+        return Err(syn::Error::new_spanned(
+            &extra.ident,
+            "only one TableData field can be the key",
+        ));
+    }
+    let row_key = keys.first().map(|f| {
+        let ident = &f.ident;
+        quote! {
+            fn row_key(&self) -> ::std::option::Option<::std::string::String> {
+                ::std::option::Option::Some(::std::string::ToString::to_string(&self.#ident))
+            }
+        }
+    });
+
+    let parents = fields.iter().filter(|f| f.parent).collect::<Vec<_>>();
+    if let Some(extra) = parents.get(1) {
+        return Err(syn::Error::new_spanned(
+            &extra.ident,
+            "only one TableData field can be the parent",
+        ));
+    }
+    let parent_key = parents.first().map(|f| {
+        let ident = &f.ident;
+        quote! {
+            fn parent_key(&self) -> ::std::option::Option<::std::string::String> {
+                ::std::option::Option::as_ref(&self.#ident).map(::std::string::ToString::to_string)
+            }
+        }
+    });
+
+    let field = format_ident!("{}Field", name);
+    let vis = &input.vis;
+    let variants = fields.iter().map(|f| &f.variant).collect::<Vec<_>>();
+    let names = fields.iter().map(|f| &f.name);
+    let field_doc = format!("Fields of `{name}` exposed to the table.");
+
+    Ok(quote! {
+        #[doc = #field_doc]
+        #[derive(::std::clone::Clone, ::std::marker::Copy, ::std::fmt::Debug, ::std::cmp::PartialEq, ::std::cmp::Eq, ::std::hash::Hash)]
+        #vis enum #field {
+            #(
+                #[allow(missing_docs)]
+                #variants,
+            )*
+        }
+
+        impl #table::types::TableField for #field {
+            fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            fn all() -> &'static [Self] {
+                &[#(Self::#variants),*]
+            }
+        }
+
+        impl #impl_generics #table::types::TableFields for #name #ty_generics #where_clause {
+            type Field = #field;
+        }
+
+        impl #impl_generics #table::types::TableData for #name #ty_generics #where_clause {
+            fn get_field_as_html(&self, field_name: &str) -> #table::error::Result<::yew::Html> {
+                match field_name {
+                    #(#html_arms)*
+                    _ => ::std::result::Result::Err(#table::error::Error::InvalidFieldName(field_name.to_string())),
+                }
+            }
+
+            fn get_field_as_value(&self, field_name: &str) -> #table::error::Result<#value> {
+                match field_name {
+                    #(#value_arms)*
+                    _ => ::std::result::Result::Err(#table::error::Error::InvalidFieldName(field_name.to_string())),
+                }
+            }
+
+            #search
+
+            #row_key
+
+            #parent_key
+
+            #render_details
+        }
+    })
+}
@@ -30,17 +30,13 @@ pub fn document() -> Option<web_sys::Document> {
 pub fn app() -> Html {
     let ctx = use_state(|| crate::types::theme::Theme { dark: true });
 
-    document().and_then(|doc| {
-        doc.document_element().and_then(|el| {
-            if ctx.get_dark() {
-                el.set_attribute("data-bs-theme", "dark").unwrap();
-            } else {
-                el.set_attribute("data-bs-theme", "danger").unwrap();
-            };
-            Some(())
-        });
-        Some(())
-    });
+    if let Some(el) = document().and_then(|doc| doc.document_element()) {
+        if ctx.get_dark() {
+            el.set_attribute("data-bs-theme", "dark").unwrap();
+        } else {
+            el.set_attribute("data-bs-theme", "danger").unwrap();
+        };
+    }
 
     html! {
         <ContextProvider<UseStateHandle<crate::types::theme::Theme>> context={ctx}>
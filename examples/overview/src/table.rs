@@ -1,11 +1,10 @@
-use std::collections::HashSet;
 use yew::{Callback, classes, function_component, Html, html, TargetCast, use_reducer, use_state};
 use serde::Serialize;
+use serde_value::Value;
 use web_sys::{HtmlInputElement, InputEvent, MouseEvent};
-use yew_hooks::use_set;
 use yew_custom_components::pagination::Pagination;
 use yew_custom_components::table::{Options, Table};
-use yew_custom_components::table::types::{ColumnBuilder, TableData};
+use yew_custom_components::table::types::{Aggregate, ColumnBuilder, ColumnGroup, Editor, ExpandMode, Export, ExportFormat, FilterKind, RowKey, SelectionMode, SortStrategy, TableData};
 
 #[function_component(TableExample)]
 pub fn table_example() -> Html {
@@ -18,14 +17,17 @@ pub fn table_example() -> Html {
     let search = (*search_term).as_ref().cloned();
 
     let page=use_state(||0usize);
-    let current_page=(*page).clone();
+    let current_page=*page;
+
+    // Number of rows matching the search
+    let filtered_count = use_state(|| 0usize);
 
     // Sum data
-    let selected_indexes = use_set(HashSet::<usize>::new());
-    let selected = selected_indexes.current().clone();
+    let selected_indexes = use_state(Vec::<RowKey>::new);
+    let selected = (*selected_indexes).clone();
 
     let sum = mock_data.data.iter().enumerate().fold(0, |acc, (index, (_, _, value))| {
-        if selected.contains(&index) {
+        if selected.contains(&RowKey::Index(index)) {
             acc + value
         } else {
             acc
@@ -42,12 +44,22 @@ pub fn table_example() -> Html {
 
 
     // Column definition
-    let columns = vec![
-        ColumnBuilder::new("select").orderable(true).short_name("Sum").data_property("select").header_class("user-select-none").build(),
-        ColumnBuilder::new("id").orderable(true).short_name("ID").data_property("id").header_class("user-select-none").build(),
-        ColumnBuilder::new("name").orderable(true).short_name("Name").data_property("name").header_class("user-select-none").build(),
-        ColumnBuilder::new("value").orderable(true).short_name("Value").data_property("value").header_class("user-select-none").build(),
-    ];
+    let columns = [
+        vec![ColumnBuilder::new("id").orderable(true).short_name("ID").field(TableLineField::Id).header_class("user-select-none").aggregate(Aggregate::Count).build()],
+        ColumnGroup::new("Line", vec![
+        ColumnBuilder::new("name").orderable(true).short_name("Name").field(TableLineField::Name).header_class("user-select-none").filter(FilterKind::Text)
+            .sort(SortStrategy::Natural)
+            .editor(Editor::Text)
+            .validate(Callback::from(|value: Value| match value {
+                Value::String(name) if name.trim().is_empty() => Err("The name cannot be empty.".to_string()),
+                _ => Ok(()),
+            }))
+            .build(),
+        ColumnBuilder::new("value").orderable(true).short_name("Value").field(TableLineField::Value).header_class("user-select-none").filter(FilterKind::NumberRange).aggregate(Aggregate::Sum)
+            .renderer(Callback::from(|line: TableLine| html!(<span class="font-monospace">{ format!("{:>6}", line.value) }</span>)))
+            .build(),
+        ]).into_columns(),
+    ].concat();
 
 
     // Table options
@@ -56,28 +68,47 @@ pub fn table_example() -> Html {
         ascending_class: Some("fa-sort-up".to_string()),
         descending_class: Some("fa-sort-down".to_string()),
         orderable_classes: vec!["mx-1".to_string(), "fa-solid".to_string()],
+        priority_classes: vec!["text-secondary".to_string()],
+        filter_classes: vec!["form-control".to_string(), "form-control-sm".to_string()],
+        selection_classes: vec!["form-check-input".to_string()],
+        toggle_classes: vec!["fa-solid".to_string()],
+        expanded_class: Some("fa-chevron-down".to_string()),
+        collapsed_class: Some("fa-chevron-right".to_string()),
+        editor_classes: vec!["form-control".to_string(), "form-control-sm".to_string()],
+        editor_error_classes: vec!["invalid-feedback".to_string(), "d-block".to_string()],
+        toolbar_classes: vec!["btn-group".to_string(), "mb-2".to_string()],
+        export_classes: vec!["btn".to_string(), "btn-outline-secondary".to_string(), "btn-sm".to_string()],
+        footer_classes: vec!["fw-bold".to_string()],
+        group_classes: vec!["table-secondary".to_string()],
+        chooser_classes: vec!["me-2".to_string()],
+        chooser_checkbox_classes: vec!["form-check-input".to_string()],
+        resize_classes: vec!["border-end".to_string()],
+        sticky_header: true,
+        max_height: Some("60vh".to_string()),
+        frozen_left: 1,
+        frozen_right: 0,
+        frozen_classes: vec!["bg-body".to_string()],
+        column_group_classes: vec!["text-center".to_string()],
+        error_placeholder: "—".to_string(),
+        skeleton_classes: vec!["placeholder".to_string(), "w-100".to_string()],
+        message_classes: vec!["text-center".to_string(), "text-muted".to_string()],
     };
 
     // Handle sum
     let callback_sum = {
         let selected_indexes = selected_indexes.clone();
-        Callback::from(move |index: usize| {
-            if !selected_indexes.insert(index) {
-                selected_indexes.remove(&index);
-            }
+        Callback::from(move |keys: Vec<RowKey>| {
+            selected_indexes.set(keys);
         })
     };
 
     // Fill the table data structure with actual data
     let mut table_data = Vec::new();
-    for (index, (id, name, value)) in mock_data.data.iter().enumerate() {
+    for (id, name, value) in mock_data.data.iter() {
         table_data.push(TableLine {
-            original_index: index,
             id: *id,
             name: name.clone(),
             value: *value,
-            checked: selected.contains(&index),
-            sum_callback: callback_sum.clone(),
         })
     }
 
@@ -178,6 +209,23 @@ pub fn table_example() -> Html {
         .active_item_classes(vec!(String::from("active")))
         .disabled_item_classes(vec!(String::from("disabled")));
 
+    let handle_filtered_count = {
+        let filtered_count = filtered_count.clone();
+        Callback::from(move |count: usize| {
+            filtered_count.set(count);
+        })
+    };
+
+    // Handle cell edits
+    let handle_cell_edit = {
+        let dispatcher = data.dispatcher();
+        Callback::from(move |(key, _, value): (RowKey, String, Value)| {
+            if let (RowKey::Index(index), Value::String(name)) = (key, value) {
+                dispatcher.dispatch(crate::types::mock_data::DataActions::RenameData(index, name));
+            }
+        })
+    };
+
     let handle_page = {
         let page = page.clone();
         Callback::from(move |id: usize| {
@@ -206,27 +254,29 @@ pub fn table_example() -> Html {
                 </span>
                 <input class="form-control" type="text" id="search" placeholder="Search" oninput={oninput_search} />
             </div>
-            <Table<TableLine> options={options.clone()} limit={Some(2)} page={current_page} search={search.clone()} classes={classes!("table", "table-hover")} columns={columns.clone()} data={table_data.clone()} orderable={true}/>
-            <Pagination total={table_data.len()} limit={2} max_pages={5} options={pagination_options} on_page={Some(handle_page)}/>
+            <Table<TableLine> options={options.clone()} limit={Some(2)} page={current_page} search={search.clone()} on_filtered_count={handle_filtered_count} classes={classes!("table", "table-hover")} columns={columns.clone()} data={table_data.clone()} orderable={true} reorderable={true} resizable={true} filterable={true} column_chooser={Some("Columns".to_string())} selection={SelectionMode::Multi} expand={ExpandMode::Multiple} on_selection_change={callback_sum} on_cell_edit={handle_cell_edit} export={Some(Export { formats: vec![ExportFormat::Csv, ExportFormat::Tsv, ExportFormat::Json], ..Export::default() })} empty={html!("No rows match your search.")}/>
+            <Pagination total={*filtered_count} limit={2} max_pages={5} options={pagination_options} on_page={Some(handle_page)}/>
             <h5>{"Sum of selected"} <span class="badge text-bg-secondary">{sum}</span></h5>
         </>
     )
 }
 
-#[derive(Clone, Serialize, Debug, Default)]
+#[derive(Clone, Serialize, Debug, Default, TableData)]
+#[table(details = "render_details")]
 struct TableLine {
-    pub original_index: usize,
     pub id: i32,
+    #[table(searchable)]
     pub name: String,
     pub value: i64,
-    pub checked: bool,
-    #[serde(skip_serializing)]
-    pub sum_callback: Callback<usize>,
+}
+
+fn render_details(line: &TableLine) -> Option<Html> {
+    Some(html!( <p class="mb-0">{ format!("{} (#{}) is worth {}", line.name, line.id, line.value) }</p> ))
 }
 
 impl PartialEq<Self> for TableLine {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.value == other.value && self.checked == other.checked
+        self.name == other.name && self.value == other.value
     }
 }
 
@@ -235,38 +285,3 @@ impl PartialOrd for TableLine {
         self.name.partial_cmp(&other.name)
     }
 }
-
-impl TableData for TableLine {
-    fn get_field_as_html(&self, field_name: &str) -> yew_custom_components::table::error::Result<Html> {
-        match field_name {
-            "select" => Ok(html!( <input type="checkbox" checked={self.checked}
-                onclick={
-                let value = self.original_index;
-                let handle_sum = self.sum_callback.clone();
-                move |_| { handle_sum.emit(value); }
-                } /> )
-            ),
-            "id" => Ok(html! { self.id }),
-            "name" => Ok(html! { self.name.clone() }),
-            "value" => Ok(html! { self.value }),
-            _ => Ok(html! {}),
-        }
-    }
-
-    fn get_field_as_value(&self, field_name: &str) -> yew_custom_components::table::error::Result<serde_value::Value> {
-        match field_name {
-            "id" => Ok(serde_value::Value::I32(self.id)),
-            "name" => Ok(serde_value::Value::String(self.name.clone())),
-            "value" => Ok(serde_value::Value::I64(self.value)),
-            "select" => Ok(serde_value::Value::Bool(self.checked)),
-            _ => Ok(serde_value::to_value(()).unwrap()),
-        }
-    }
-
-    fn matches_search(&self, needle: Option<String>) -> bool {
-        match needle {
-            Some(needle) => self.name.to_lowercase().contains(&needle.to_lowercase()),
-            None => true,
-        }
-    }
-}
@@ -36,10 +36,12 @@ impl Default for Data {
     }
 }
 
+#[allow(clippy::enum_variant_names)]
 pub enum DataActions {
     AddData((i32, String, i64)),
     #[allow(dead_code)]
     RemoveData(i32),
+    RenameData(usize, String),
     RandomizeData,
 }
 
@@ -56,9 +58,14 @@ impl yew::Reducible for Data {
             DataActions::RemoveData(id) => {
                 new.data.retain(|(i, _, _)| i != &id);
             }
+            DataActions::RenameData(index, name) => {
+                if let Some((_, n, _)) = new.data.get_mut(index) {
+                    *n = name;
+                }
+            }
             DataActions::RandomizeData => {
                 for (_, _, x) in new.data.iter_mut() {
-                    *x = (*x) + rng.gen_range(-5..5);
+                    *x += rng.gen_range(-5..5);
                 }
             }
         }
@@ -159,20 +159,15 @@ pub fn pagination(props: &Props) -> Html {
         })
     };
 
-    let total_pages = props.total / props.limit;
-    let total_pages = if props.total % props.limit == 0 {
-        total_pages
-    } else {
-        total_pages + 1
-    };
+    let total_pages = props.total.div_ceil(props.limit);
 
     let pages = match props.max_pages {
         None => {0..total_pages}
         Some(max) => {
             let start = if current_page < max/2 {
                 0
-            } else if current_page >= total_pages - max/2 {
-                total_pages - max
+            } else if current_page >= total_pages.saturating_sub(max/2) {
+                total_pages.saturating_sub(max)
             } else {
                 current_page - max/2
             };
@@ -237,7 +232,7 @@ pub fn pagination(props: &Props) -> Html {
                 {
 
                     if options.show_prev_next {
-                        let disabled_class = if current_page==total_pages-1 {
+                        let disabled_class = if current_page==total_pages.saturating_sub(1) {
                             Some(options.disabled_item_classes.clone())
                         } else {
                             None
@@ -251,13 +246,13 @@ pub fn pagination(props: &Props) -> Html {
                 }
                 {
                     if options.show_first_last {
-                        let disabled_class = if current_page==total_pages-1 {
+                        let disabled_class = if current_page==total_pages.saturating_sub(1) {
                             Some(options.disabled_item_classes.clone())
                         } else {
                             None
                         };
                         html! {
-                            <li class={classes!(options.item_classes.clone(), disabled_class)}><a class={classes!(options.link_classes.clone())} onclick={ let handle_page= handle_page.clone(); move |_| { handle_page.emit(total_pages-1); }} href="#">{  options.last_text }</a></li>
+                            <li class={classes!(options.item_classes.clone(), disabled_class)}><a class={classes!(options.link_classes.clone())} onclick={ let handle_page= handle_page.clone(); move |_| { handle_page.emit(total_pages.saturating_sub(1)); }} href="#">{  options.last_text }</a></li>
                         }
                     } else {
                         html!()
@@ -1,71 +1,611 @@
-use super::types::{TableData, TableOrder};
+use super::editor::CellEditor;
+use super::error::{Error, Result};
+use super::types::{
+    Collator, Column, ExpandMode, GroupPagination, Nulls, RowKey, SelectionMode, SortStrategy,
+    TableData, TableOrder,
+};
+use super::value::as_string;
+use super::{Callbacks, Options};
+use serde_value::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
-use yew::{function_component, html, use_context, Html, Properties, UseReducerHandle};
+use std::ops::Range;
+use std::rc::Rc;
+use web_sys::Element;
+use yew::{
+    classes, function_component, html, use_context, use_effect_with, use_state, use_state_eq,
+    Callback, Children, ContextProvider, Event, Html, KeyboardEvent, MouseEvent, Properties,
+    TargetCast, UseReducerHandle,
+};
+
+/// Rows of the table after search, filters, sorting and pagination.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Default)]
+pub(crate) struct View<T>
+where
+    T: TableData + Debug,
+{
+    /// Rows matching the search and the filters, sorted, with their keys.
+    pub(crate) rows: Vec<(RowKey, T)>,
+    /// Range of `rows` displayed on the current page.
+    pub(crate) page: Range<usize>,
+    /// Number of rows matching the search and the filters, or of top-level groups when the
+    /// pagination counts groups.
+    pub(crate) count: usize,
+    /// Values of the grouping properties of each row, empty when the rows are not grouped.
+    pub(crate) paths: Vec<Vec<Value>>,
+    /// Range of `rows` of each group, keyed by the values of its path.
+    pub(crate) groups: BTreeMap<Vec<Value>, Range<usize>>,
+    /// Depth of each row in the tree, empty when the table is not a tree.
+    pub(crate) depths: BTreeMap<RowKey, usize>,
+    /// Errors met while filtering, sorting and grouping the rows.
+    pub(crate) errors: Vec<Error>,
+    /// Keys of the rows with children in the tree.
+    pub(crate) parents: BTreeSet<RowKey>,
+}
+
+#[cfg(feature="table")]
+impl<T> View<T>
+where
+    T: TableData + Debug,
+{
+    /// Builds the view of the table.
+    pub(crate) fn new(
+        state: &super::Data<T>,
+        search: &super::Search,
+        pagination: &super::Pagination,
+        total: Option<usize>,
+    ) -> Self {
+        let keyed: Vec<(RowKey, T)> = state
+            .data
+            .iter()
+            .enumerate()
+            .map(|(index, row)| (RowKey::new(index, row), row.clone()))
+            .collect();
+        let mut errors = duplicate_keys(&keyed);
+        if state.remote {
+            return Self {
+                page: 0..keyed.len(),
+                count: total.unwrap_or(keyed.len()),
+                rows: keyed,
+                errors,
+                ..Self::default()
+            };
+        }
+
+        if state.tree {
+            let (matched, filter_errors) = filtered_rows(state, keyed.iter().cloned(), search.search.clone());
+            let (rows, sort_errors) = sorted_rows(state, with_ancestors(&keyed, matched));
+            errors.extend(filter_errors);
+            errors.extend(sort_errors);
+            let (rows, depths, parents) = tree_rows(state, rows);
+            return Self {
+                page: page_range(pagination, rows.len()),
+                count: rows.len(),
+                rows,
+                depths,
+                parents,
+                errors,
+                ..Self::default()
+            };
+        }
+
+        let (rows, filter_errors) = filtered_rows(state, keyed.into_iter(), search.search.clone());
+        let (rows, sort_errors) = sorted_rows(state, rows);
+        errors.extend(filter_errors);
+        errors.extend(sort_errors);
+        // Rows whose group cannot be read are grouped under an empty value.
+        let paths: Vec<Vec<Value>> = rows
+            .iter()
+            .map(|(key, row)| {
+                state
+                    .group_by
+                    .iter()
+                    .map(|property| {
+                        row.get_field_as_value(property).unwrap_or_else(|e| {
+                            if !errors.contains(&e) {
+                                log::error!("Failed to read the group of row {key}: {e}");
+                                errors.push(e);
+                            }
+                            Value::Unit
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut groups: BTreeMap<Vec<Value>, Range<usize>> = BTreeMap::new();
+        for (index, path) in paths.iter().enumerate() {
+            for depth in 1..=path.len() {
+                groups
+                    .entry(path[..depth].to_vec())
+                    .and_modify(|range| range.end = index + 1)
+                    .or_insert(index..index + 1);
+            }
+        }
+
+        let (page, count) = if state.group_by.is_empty()
+            || state.group_pagination == GroupPagination::Rows
+        {
+            (page_range(pagination, rows.len()), rows.len())
+        } else {
+            let mut top: Vec<&Range<usize>> = groups
+                .iter()
+                .filter(|(path, _)| path.len() == 1)
+                .map(|(_, range)| range)
+                .collect();
+            top.sort_by_key(|range| range.start);
+            let top_page = page_range(pagination, top.len());
+            let page = if top_page.is_empty() {
+                rows.len()..rows.len()
+            } else {
+                top[top_page.start].start..top[top_page.end - 1].end
+            };
+            (page, top.len())
+        };
+        Self {
+            page,
+            count,
+            rows,
+            paths,
+            groups,
+            errors,
+            ..Self::default()
+        }
+    }
+
+    /// Returns true if one of the first `depth` groups of the row at the index is collapsed.
+    fn collapsed(&self, collapsed: &BTreeSet<Vec<Value>>, index: usize, depth: usize) -> bool {
+        let path = &self.paths[index];
+        (1..=depth.min(path.len())).any(|d| collapsed.contains(&path[..d]))
+    }
+
+    /// Returns the rows of the current page.
+    pub(crate) fn page_rows(&self) -> &[(RowKey, T)] {
+        &self.rows[self.page.clone()]
+    }
+}
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub struct BodyProps {
+    #[prop_or(false)]
+    pub group_aggregates: bool,
+    #[prop_or(false)]
+    pub loading: bool,
+    /// Number of skeleton rows rendered while loading.
+    #[prop_or(0)]
+    pub skeleton_rows: usize,
+    #[prop_or_default]
+    pub empty: Html,
+    #[prop_or_default]
+    pub error: Option<Html>,
+}
 
 #[cfg(feature="table")]
 #[function_component(TableBody)]
-pub fn table_body<T>() -> Html
+pub fn table_body<T>(props: &BodyProps) -> Html
 where
     T: TableData + Debug,
 {
     let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
-    let pagination = use_context::<super::Pagination>().unwrap();
-    let mut data = state.data.clone();
-    let columns = state.columns.clone();
-    let order = state.state.order.clone();
-    if let Some(index) = order
-        .iter()
-        .enumerate()
-        .filter_map(|(i, o)| match o {
-            super::TableOrder::Ascending => Some(i),
-            super::TableOrder::Descending => Some(i),
-            _ => None,
-        })
-        .nth(0)
-    {
-        if let Some(column) = columns.get(index) {
-            if let Some(data_property) = column.data_property.as_ref() {
-                if let Some(ord) = order.get(index) {
-                    match ord {
-                        TableOrder::Unordered => {}
-                        TableOrder::Ascending => {
-                            data.sort_by_cached_key(|x| {
-                                x.get_field_as_value(data_property).unwrap()
-                            });
-                        }
-                        TableOrder::Descending => {
-                            data.sort_by_cached_key(|x| {
-                                std::cmp::Reverse(x.get_field_as_value(data_property).unwrap())
-                            });
-                        }
-                    }
+    let view = use_context::<Rc<View<T>>>().unwrap();
+    let viewport = use_context::<super::Viewport>().unwrap_or_default();
+    let options = use_context::<Options>().unwrap();
+    let data = view.page_rows();
+    let message = |content: Html| {
+        html!(<tbody>
+            <tr>
+                <td colspan={state.column_count().to_string()} class={classes!(options.message_classes.clone())}>{ content }</td>
+            </tr>
+            </tbody>)
+    };
+    if let Some(error) = props.error.clone() {
+        return message(error);
+    }
+    if props.loading {
+        let skeleton = html!(<span class={classes!(options.skeleton_classes.clone())} style="display: block;">{"\u{a0}"}</span>);
+        return html!(<tbody>
+            {for (0..props.skeleton_rows).map(|_| html! {
+                <tr>
+                    {for (0..state.column_count()).map(|_| html!(<td>{ skeleton.clone() }</td>))}
+                </tr>
+            })}
+            </tbody>);
+    }
+    if view.rows.is_empty() {
+        return message(props.empty.clone());
+    }
+
+    let row = |(key, row): &(RowKey, T)| {
+        html!(<Row<T> key={key.html_key()} row={row.clone()} row_key={key.clone()} selected={state.selected.contains(key)} expanded={state.expanded.contains(key)} depth={view.depths.get(key).copied()} parent={view.parents.contains(key)} />)
+    };
+
+    if !view.groups.is_empty() {
+        let mut rows = vec![];
+        for index in view.page.clone() {
+            let path = &view.paths[index];
+            let shared = index
+                .checked_sub(1)
+                .filter(|previous| *previous >= view.page.start)
+                .map_or(0, |previous| {
+                    view.paths[previous]
+                        .iter()
+                        .zip(path)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+            for depth in shared..path.len() {
+                if view.collapsed(&state.collapsed, index, depth) {
+                    break;
                 }
+                let group = path[..=depth].to_vec();
+                let key = format!("group:{group:?}");
+                let range = view.groups[&group].clone();
+                rows.push(html!(<GroupRow<T> {key} rows={range} path={group} aggregates={props.group_aggregates} />));
+            }
+            if !view.collapsed(&state.collapsed, index, path.len()) {
+                rows.push(row(&view.rows[index]));
             }
         }
+        return html!(<tbody>{for rows}</tbody>);
+    }
+
+    let Some(virtual_scroll) = viewport.virtual_scroll else {
+        return html!(<tbody>
+            {for data.iter().map(row)}
+            </tbody>);
     };
 
-    if let Some(limit) = pagination.limit {
-        data = data
-            .into_iter()
-            .skip(pagination.page * limit)
-            .take(limit)
-            .collect();
-    }
+    let visible = (virtual_scroll.height / virtual_scroll.row_height.max(1)) as usize + 1;
+    let start = viewport
+        .first_row
+        .saturating_sub(virtual_scroll.overscan)
+        .min(data.len());
+    let end = (viewport.first_row + visible + virtual_scroll.overscan).min(data.len());
+    let spacer = |rows: usize| {
+        let height = rows * virtual_scroll.row_height as usize;
+        html!(<tr style={format!("height: {height}px;")}></tr>)
+    };
 
     html!(<tbody>
-        {for data.iter().map(|row| {
-            html!(<Row<T> row={row.clone()} />)
-        })}
+        if start > 0 {
+            { spacer(start) }
+        }
+        {for data[start..end].iter().map(row)}
+        if end < data.len() {
+            { spacer(data.len() - end) }
+        }
         </tbody>)
 }
 
 #[cfg(feature="table")]
-#[derive(Properties, Clone, Eq, PartialEq, Default)]
+#[derive(Properties, Clone, PartialEq)]
+pub struct ScrollerProps {
+    pub virtual_scroll: super::VirtualScroll,
+    pub children: Children,
+}
+
+/// Scroll container of a virtually scrolled table.
+///
+/// It keeps the scroll position itself, so that scrolling only renders the rows again and not
+/// the whole table.
+#[cfg(feature="table")]
+#[function_component(Scroller)]
+pub fn scroller(props: &ScrollerProps) -> Html {
+    let first_row = use_state_eq(|| 0usize);
+    let virtual_scroll = props.virtual_scroll;
+    let viewport = super::Viewport {
+        virtual_scroll: Some(virtual_scroll),
+        first_row: *first_row,
+    };
+    let onscroll = {
+        let row_height = virtual_scroll.row_height.max(1);
+        Callback::from(move |e: Event| {
+            let element: Element = e.target_unchecked_into();
+            first_row.set(usize::try_from(element.scroll_top() / row_height as i32).unwrap_or(0));
+        })
+    };
+    html!(
+        <ContextProvider<super::Viewport> context={viewport}>
+            <div style={format!("height: {}px; overflow: auto;", virtual_scroll.height)} {onscroll}>
+                { props.children.clone() }
+            </div>
+        </ContextProvider<super::Viewport>>
+    )
+}
+
+/// Returns an error for each key shared by several rows, which would confuse the rendering and
+/// the selection of the rows.
+#[cfg(feature="table")]
+pub(crate) fn duplicate_keys<T>(rows: &[(RowKey, T)]) -> Vec<Error> {
+    let mut keys = BTreeSet::new();
+    let mut errors = vec![];
+    for (key, _) in rows {
+        let error = Error::DuplicateRowKey(key.to_string());
+        if !keys.insert(key) && !errors.contains(&error) {
+            log::error!("{error}");
+            errors.push(error);
+        }
+    }
+    errors
+}
+
+/// Returns the rows matching the search and the column filters, along with the errors met while
+/// reading the filtered values.
+///
+/// Rows whose filtered values cannot be read do not match.
+#[cfg(feature="table")]
+pub(crate) fn filtered_rows<T>(
+    state: &super::Data<T>,
+    data: impl Iterator<Item = (RowKey, T)>,
+    search: Option<String>,
+) -> (Vec<(RowKey, T)>, Vec<Error>)
+where
+    T: TableData + Debug,
+{
+    let filters = state.column_filters();
+    let mut errors = vec![];
+    let rows = data
+        .filter(|(_, row)| row.matches_search(search.clone()))
+        .filter(|(key, row)| {
+            filters.iter().all(|(column, filter)| match column.value_of(row) {
+                Ok(value) => filter.matches(&value),
+                Err(e) => {
+                    if !errors.contains(&e) {
+                        log::error!("Failed to read the filtered value of row {key}: {e}");
+                        errors.push(e);
+                    }
+                    false
+                }
+            })
+        })
+        .collect();
+    (rows, errors)
+}
+
+/// Returns the matching rows along with their ancestors, in the order of the data.
+#[cfg(feature="table")]
+pub(crate) fn with_ancestors<T>(all: &[(RowKey, T)], matched: Vec<(RowKey, T)>) -> Vec<(RowKey, T)>
+where
+    T: TableData + Debug,
+{
+    let parents: BTreeMap<&RowKey, RowKey> = all
+        .iter()
+        .filter_map(|(key, row)| Some((key, RowKey::Key(row.parent_key()?))))
+        .collect();
+    let mut included: BTreeSet<RowKey> = BTreeSet::new();
+    for (key, _) in matched {
+        let mut key = Some(key);
+        while let Some(current) = key.take() {
+            if !included.insert(current.clone()) {
+                break;
+            }
+            key = parents.get(&current).cloned();
+        }
+    }
+    all.iter()
+        .filter(|(key, _)| included.contains(key))
+        .cloned()
+        .collect()
+}
+
+/// Orders the sorted rows depth first, keeping the order of the siblings.
+///
+/// Rows whose parent is missing are roots, as are the first rows of parent chains forming a
+/// cycle, so that every row shows. Rows under a collapsed row are left out. Returns the rows with
+/// the depth of each row and the keys of the rows with children.
+#[cfg(feature="table")]
+#[allow(clippy::type_complexity)]
+pub(crate) fn tree_rows<T>(
+    state: &super::Data<T>,
+    rows: Vec<(RowKey, T)>,
+) -> (Vec<(RowKey, T)>, BTreeMap<RowKey, usize>, BTreeSet<RowKey>)
+where
+    T: TableData + Debug,
+{
+    let keys: BTreeSet<RowKey> = rows.iter().map(|(key, _)| key.clone()).collect();
+    let mut roots = vec![];
+    let mut children: BTreeMap<RowKey, Vec<usize>> = BTreeMap::new();
+    for (index, (_, row)) in rows.iter().enumerate() {
+        match row.parent_key().map(RowKey::Key).filter(|parent| keys.contains(parent)) {
+            Some(parent) => children.entry(parent).or_default().push(index),
+            None => roots.push(index),
+        }
+    }
+
+    let mut reached = vec![false; rows.len()];
+    let mut cycles = vec![];
+    // Rows still unreached once the roots are walked belong to cycles.
+    for (position, index) in roots.iter().copied().chain(0..rows.len()).enumerate() {
+        if reached[index] {
+            continue;
+        }
+        if position >= roots.len() {
+            cycles.push(index);
+        }
+        let mut stack = vec![index];
+        while let Some(index) = stack.pop() {
+            if !std::mem::replace(&mut reached[index], true) {
+                stack.extend(children.get(&rows[index].0).into_iter().flatten());
+            }
+        }
+    }
+    roots.extend(cycles);
+    roots.sort_unstable();
+
+    let mut ordered = vec![];
+    let mut depths = BTreeMap::new();
+    let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|index| (index, 0)).collect();
+    while let Some((index, depth)) = stack.pop() {
+        let key = &rows[index].0;
+        if depths.contains_key(key) {
+            continue;
+        }
+        depths.insert(key.clone(), depth);
+        ordered.push(index);
+        if !state.collapsed_nodes.contains(key) {
+            if let Some(indexes) = children.get(key) {
+                stack.extend(indexes.iter().rev().map(|index| (*index, depth + 1)));
+            }
+        }
+    }
+
+    let parents = children.into_keys().collect();
+    let rows = ordered.into_iter().map(|index| rows[index].clone()).collect();
+    (rows, depths, parents)
+}
+
+/// Returns the rows sorted by the sort keys of the table, along with the errors met while
+/// reading the keys.
+///
+/// Rows whose keys cannot be read come last, in their original order.
+#[cfg(feature="table")]
+pub(crate) fn sorted_rows<T>(
+    state: &super::Data<T>,
+    data: Vec<(RowKey, T)>,
+) -> (Vec<(RowKey, T)>, Vec<Error>)
+where
+    T: TableData + Debug,
+{
+    let groups: &[String] = if state.tree { &[] } else { &state.group_by };
+    let columns = state.sort_columns();
+    let strategy = SortStrategy::default();
+    let keys: Vec<(&SortStrategy, Nulls, TableOrder)> = groups
+        .iter()
+        .map(|_| (&strategy, Nulls::default(), TableOrder::Ascending))
+        .chain(columns.iter().map(|(column, order)| (&column.sort, column.nulls, *order)))
+        .collect();
+    if keys.is_empty() {
+        return (data, vec![]);
+    }
+    let collator = keys
+        .iter()
+        .any(|(strategy, ..)| **strategy == SortStrategy::Locale)
+        .then(Collator::new);
+
+    let mut errors = vec![];
+    let mut keyed: Vec<_> = data
+        .into_iter()
+        .map(|x| {
+            let values: Result<Vec<Value>> = groups
+                .iter()
+                .map(|property| x.1.get_field_as_value(property))
+                .chain(columns.iter().map(|(column, _)| column.value_of(&x.1)))
+                .collect();
+            let values = values
+                .map_err(|e| {
+                    if !errors.contains(&e) {
+                        log::error!("Failed to read the sort key of row {}: {e}", x.0);
+                        errors.push(e);
+                    }
+                })
+                .ok();
+            (values, x)
+        })
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => keys
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .fold(Ordering::Equal, |acc, ((strategy, nulls, order), (a, b))| {
+                acc.then_with(|| nulls.compare_with(strategy, *order, a, b, collator.as_ref()))
+            }),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    });
+    (keyed.into_iter().map(|(_, x)| x).collect(), errors)
+}
+
+/// Returns the range of the rows displayed on the current page.
+#[cfg(feature="table")]
+pub(crate) fn page_range(pagination: &super::Pagination, len: usize) -> Range<usize> {
+    match pagination.limit {
+        Some(limit) => {
+            let start = (pagination.page * limit).min(len);
+            start..(start + limit).min(len)
+        }
+        None => 0..len,
+    }
+}
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub struct GroupProps {
+    pub path: Vec<Value>,
+    pub rows: Range<usize>,
+    #[prop_or(false)]
+    pub aggregates: bool,
+}
+
+/// Header row of a group, with its value, its row count and a collapse toggle.
+#[cfg(feature="table")]
+#[function_component(GroupRow)]
+pub fn group_row<T>(props: &GroupProps) -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let view = use_context::<Rc<View<T>>>().unwrap();
+    let options = use_context::<Options>().unwrap();
+    let onclick = {
+        let dispatch = state.dispatcher();
+        let path = props.path.clone();
+        move |_| dispatch.dispatch(super::Msg::ToggleGroup(path.clone()))
+    };
+    let toggle_class = if state.collapsed.contains(&props.path) {
+        options.collapsed_class.clone()
+    } else {
+        options.expanded_class.clone()
+    };
+    let label = props.path.last().and_then(as_string).unwrap_or_default();
+    let span = if props.aggregates {
+        state.leading_columns() + 1
+    } else {
+        state.column_count()
+    };
+    let rows = &view.rows[props.rows.clone()];
+
+    html!(
+        <tr class={classes!(options.group_classes.clone())}>
+            <td colspan={span.to_string()}>
+                <span style={format!("padding-left: {}em;", (props.path.len() - 1) * 2)}>
+                    <i class={classes!(options.toggle_classes.clone(), toggle_class)} role="button" {onclick}></i>
+                    { format!(" {label} ({})", props.rows.len()) }
+                </span>
+            </td>
+            if props.aggregates {
+                {for state.visible_columns().skip(1).map(|(_, column)| {
+                    let Some(aggregate) = &column.aggregate else {
+                        return html! { <td></td> };
+                    };
+                    let values: Vec<Value> = rows
+                        .iter()
+                        .filter_map(|(_, row)| column.value_of(row).ok())
+                        .collect();
+                    html! { <td>{ aggregate.render(&values) }</td> }
+                })}
+            }
+        </tr>
+    )
+}
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
 pub struct Props<T>
 where
     T: TableData + Debug,
 {
     pub row: T,
+    pub row_key: RowKey,
+    #[prop_or(false)]
+    pub selected: bool,
+    #[prop_or(false)]
+    pub expanded: bool,
+    /// Depth of the row when the table is a tree.
+    #[prop_or_default]
+    pub depth: Option<usize>,
+    #[prop_or(false)]
+    pub parent: bool,
 }
 
 #[cfg(feature="table")]
@@ -75,21 +615,360 @@ where
     T: TableData + Debug,
 {
     let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let view = use_context::<Rc<View<T>>>().unwrap();
+    let viewport = use_context::<super::Viewport>().unwrap_or_default();
+    let options = use_context::<Options>().unwrap();
+    let callbacks = use_context::<Callbacks>().unwrap();
+    let editing = use_state(|| None::<String>);
     let columns = state.columns.clone();
-    let search = use_context::<super::Search>().unwrap();
     let row = props.row.clone();
+    let style = viewport
+        .virtual_scroll
+        .map(|v| format!("height: {}px;", v.row_height));
 
-    if row.matches_search(search.search.clone()) {
-        html!(<tr>
-                    {
-                        for columns.iter()
-                            .map(|c| { c.data_property.as_ref().unwrap_or(&c.name) })
-                            .map(|name| { row.get_field_as_html(name) })
-                            .filter_map(std::result::Result::ok)
-                            .map(|el| html! { <td>{ el }</td> })
-                    }
-                </tr>)
+    let onclick = {
+        let state = state.clone();
+        let key = props.row_key.clone();
+        let multi = state.selection == SelectionMode::Multi;
+        move |e: MouseEvent| {
+            let range = state.anchor.as_ref().filter(|_| multi && e.shift_key()).and_then(|anchor| {
+                let keys: Vec<&RowKey> = view.rows.iter().map(|(k, _)| k).collect();
+                let from = keys.iter().position(|k| *k == anchor)?;
+                let to = keys.iter().position(|k| **k == key)?;
+                Some(keys[from.min(to)..=from.max(to)].iter().map(|k| (*k).clone()).collect::<Vec<_>>())
+            });
+            // Selecting a range already selected would not render again and leave the checkbox
+            // unticked by the browser, so the click toggles the row like the checkbox does.
+            match range.filter(|range| !range.iter().all(|k| state.selected.contains(k))) {
+                Some(range) => state.dispatch(super::Msg::SetRowsSelected(range, true)),
+                None => state.dispatch(super::Msg::ToggleRow(key.clone())),
+            }
+        }
+    };
+    let details = if state.expand == ExpandMode::None {
+        None
+    } else {
+        row.render_details()
+    };
+    let ontoggle = {
+        let dispatch = state.dispatcher();
+        let key = props.row_key.clone();
+        move |_| dispatch.dispatch(super::Msg::ToggleDetails(key.clone()))
+    };
+    let toggle_class = if props.expanded {
+        options.expanded_class.clone()
     } else {
-        html!()
+        options.collapsed_class.clone()
+    };
+    let input_type = if state.selection == SelectionMode::Single {
+        "radio"
+    } else {
+        "checkbox"
+    };
+    let first = state.visible_columns().next().map(|(index, _)| index);
+    let leading = state.leading_columns();
+    let cells: Vec<(usize, usize, &Column<T>, Result<Html>)> = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.visible)
+        .enumerate()
+        .map(|(position, (index, c))| (leading + position, index, c, c.html_of(&row)))
+        .collect();
+    {
+        let errors: Vec<Error> = cells
+            .iter()
+            .filter_map(|(.., el)| el.as_ref().err().cloned())
+            .collect();
+        let on_error = callbacks.on_error.clone();
+        let key = props.row_key.clone();
+        use_effect_with(errors, move |errors| {
+            for e in errors {
+                log::error!("Failed to render a cell of row {key}: {e}");
+                on_error.emit(e.clone());
+            }
+        });
+    }
+    let frozen = state.frozen_styles(&options);
+    let frozen_style = |position: usize| frozen.get(position).cloned().flatten();
+    let frozen_class = |position: usize| {
+        classes!(frozen.get(position).cloned().flatten().map(|_| options.frozen_classes.clone()))
+    };
+    let node = |el: Html| {
+        let Some(depth) = props.depth else {
+            return el;
+        };
+        let onclick = {
+            let dispatch = state.dispatcher();
+            let key = props.row_key.clone();
+            move |_| dispatch.dispatch(super::Msg::ToggleNode(key.clone()))
+        };
+        let node_class = if state.collapsed_nodes.contains(&props.row_key) {
+            options.collapsed_class.clone()
+        } else {
+            options.expanded_class.clone()
+        };
+        html!(<>
+            <span style={format!("display: inline-block; width: {}em;", depth * 2 + 2)}>
+                if props.parent {
+                    <i class={classes!(options.toggle_classes.clone(), node_class)} role="button" {onclick}></i>
+                }
+            </span>
+            { el }
+        </>)
+    };
+
+    html!(<>
+            <tr {style}>
+                if state.expand != ExpandMode::None {
+                    <td style={frozen_style(0)} class={frozen_class(0)}>
+                        if details.is_some() {
+                            <i class={classes!(options.toggle_classes.clone(), toggle_class)} role="button" onclick={ontoggle}></i>
+                        }
+                    </td>
+                }
+                if state.selection != SelectionMode::None {
+                    <td style={frozen_style(leading - 1)} class={frozen_class(leading - 1)}>
+                        <input type={input_type} class={classes!(options.selection_classes.clone())} checked={props.selected} {onclick} />
+                    </td>
+                }
+                {
+                    for cells.into_iter()
+                        .map(|(position, index, c, el)| (position, index, c, el.unwrap_or_else(|_| html!({ options.error_placeholder.clone() }))))
+                        .map(|(position, index, c, el)| (position, c, c.property(), if Some(index) == first { node(el) } else { el }))
+                        .map(|(position, c, name, el)| {
+                            let (style, class) = (frozen_style(position), frozen_class(position));
+                            let Some(editor) = c.editor.clone() else {
+                                return html! { <td {style} {class}>{ el }</td> };
+                            };
+                            if editing.as_deref() == Some(name) {
+                                let on_commit = {
+                                    let on_cell_edit = callbacks.on_cell_edit.clone();
+                                    let editing = editing.clone();
+                                    let key = props.row_key.clone();
+                                    let name = name.to_string();
+                                    Callback::from(move |value| {
+                                        editing.set(None);
+                                        on_cell_edit.emit((key.clone(), name.clone(), value));
+                                    })
+                                };
+                                let on_cancel = {
+                                    let editing = editing.clone();
+                                    Callback::from(move |()| editing.set(None))
+                                };
+                                let value = c.value_of(&row).unwrap_or(Value::Unit);
+                                return html! {
+                                    <td {style} {class}>
+                                        <CellEditor {editor} {value} validate={c.validate.clone()} {on_commit} {on_cancel} />
+                                    </td>
+                                };
+                            }
+                            let ondblclick = {
+                                let editing = editing.clone();
+                                let name = name.to_string();
+                                move |_| editing.set(Some(name.clone()))
+                            };
+                            let onkeydown = {
+                                let editing = editing.clone();
+                                let name = name.to_string();
+                                move |e: KeyboardEvent| {
+                                    if e.key() == "Enter" {
+                                        e.prevent_default();
+                                        editing.set(Some(name.clone()));
+                                    }
+                                }
+                            };
+                            html! { <td tabindex="0" {style} {class} {ondblclick} {onkeydown}>{ el }</td> }
+                        })
+                }
+            </tr>
+            if let Some(details) = details.filter(|_| props.expanded) {
+                <tr>
+                    <td colspan={state.column_count().to_string()}>{ details }</td>
+                </tr>
+            }
+        </>)
+}
+
+#[cfg(all(test, feature="table"))]
+mod tests {
+    use super::super::tests::{keyed, line, Line};
+    use super::super::types::{ColumnBuilder, ColumnFilter};
+    use super::super::{Data, Pagination, Search};
+    use super::*;
+
+    fn grouped(group_by: &[&str], pagination: GroupPagination) -> Data<Line> {
+        Data {
+            data: Rc::new(vec![
+                line(1, None, "x", "c"),
+                line(2, None, "x", "a"),
+                line(3, None, "y", "b"),
+                line(4, None, "y", "a"),
+                line(5, None, "x", "c"),
+            ]),
+            group_by: group_by.iter().map(ToString::to_string).collect(),
+            group_pagination: pagination,
+            ..Data::default()
+        }
+    }
+
+    fn view(state: &Data<Line>, limit: Option<usize>, page: usize) -> View<Line> {
+        View::new(state, &Search::default(), &Pagination { limit, page }, None)
+    }
+
+    fn ids(view: &View<Line>) -> Vec<u32> {
+        view.page_rows().iter().map(|(_, line)| line.id).collect()
+    }
+
+    fn tree(lines: &[Line]) -> (Vec<u32>, Vec<usize>) {
+        let state = Data::<Line>::default();
+        let (rows, depths, _) = tree_rows(&state, keyed(lines));
+        rows.iter().map(|(key, line)| (line.id, depths[key])).unzip()
+    }
+
+    #[test]
+    fn tree_rows_are_ordered_depth_first() {
+        let lines = [
+            line(3, Some(1), "c", ""),
+            line(1, None, "a", ""),
+            line(4, Some(3), "d", ""),
+            line(2, None, "b", ""),
+            line(5, Some(1), "e", ""),
+        ];
+        assert_eq!(tree(&lines), (vec![1, 3, 4, 5, 2], vec![0, 1, 2, 1, 0]));
+    }
+
+    #[test]
+    fn orphans_are_roots() {
+        let lines = [line(1, Some(9), "a", ""), line(2, Some(1), "b", "")];
+        assert_eq!(tree(&lines), (vec![1, 2], vec![0, 1]));
+    }
+
+    #[test]
+    fn cycles_are_broken_at_their_first_row() {
+        let lines = [
+            line(1, None, "a", ""),
+            line(2, Some(3), "b", ""),
+            line(3, Some(2), "c", ""),
+            line(4, Some(3), "d", ""),
+            line(5, Some(5), "e", ""),
+        ];
+        assert_eq!(tree(&lines), (vec![1, 2, 3, 4, 5], vec![0, 0, 1, 2, 0]));
+    }
+
+    #[test]
+    fn collapsed_rows_hide_their_descendants() {
+        let mut state = Data::<Line>::default();
+        state.collapsed_nodes.insert(RowKey::Key("1".to_string()));
+        let lines = [line(1, None, "a", ""), line(2, Some(1), "b", ""), line(3, None, "c", "")];
+        let (rows, _, parents) = tree_rows(&state, keyed(&lines));
+        let ids: Vec<u32> = rows.iter().map(|(_, line)| line.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert!(parents.contains(&RowKey::Key("1".to_string())));
+    }
+
+    #[test]
+    fn search_hits_keep_their_ancestors() {
+        let lines = [
+            line(1, None, "root", ""),
+            line(2, Some(1), "branch", ""),
+            line(3, Some(2), "leaf", ""),
+            line(4, None, "other", ""),
+            line(5, Some(9), "orphan leaf", ""),
+        ];
+        let all = keyed(&lines);
+        let matched = all.iter().filter(|(_, l)| l.name.contains("leaf")).cloned().collect();
+        let ids: Vec<u32> = with_ancestors(&all, matched).iter().map(|(_, l)| l.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn ancestors_of_cycles_are_found_once() {
+        let lines = [line(1, Some(2), "a", ""), line(2, Some(1), "b", ""), line(3, None, "c", "")];
+        let all = keyed(&lines);
+        let matched = vec![all[0].clone()];
+        let ids: Vec<u32> = with_ancestors(&all, matched).iter().map(|(_, l)| l.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn page_range_is_clamped_to_the_rows() {
+        let pagination = |limit, page| Pagination { limit, page };
+        assert_eq!(page_range(&pagination(Some(2), 1), 5), 2..4);
+        assert_eq!(page_range(&pagination(Some(2), 2), 5), 4..5);
+        assert_eq!(page_range(&pagination(Some(2), 3), 5), 5..5);
+        assert_eq!(page_range(&pagination(None, 3), 5), 0..5);
+    }
+
+    #[test]
+    fn grouped_rows_are_contiguous_and_paginated_by_rows() {
+        let state = grouped(&["group"], GroupPagination::Rows);
+        let view = view(&state, Some(2), 1);
+        assert_eq!(view.count, 5);
+        assert_eq!(ids(&view), vec![3, 1]);
+        let string = |s: &str| vec![Value::String(s.to_string())];
+        assert_eq!(view.groups[&string("a")], 0..2);
+        assert_eq!(view.groups[&string("b")], 2..3);
+        assert_eq!(view.groups[&string("c")], 3..5);
+    }
+
+    #[test]
+    fn pagination_by_groups_counts_and_slices_top_level_groups() {
+        let state = grouped(&["group"], GroupPagination::Groups);
+        let first = view(&state, Some(2), 0);
+        assert_eq!(first.count, 3);
+        assert_eq!(ids(&first), vec![2, 4, 3]);
+        assert_eq!(ids(&view(&state, Some(2), 1)), vec![1, 5]);
+        assert!(ids(&view(&state, Some(2), 2)).is_empty());
+    }
+
+    #[test]
+    fn nested_groups_have_their_own_ranges() {
+        let state = grouped(&["group", "name"], GroupPagination::Groups);
+        let view = view(&state, Some(1), 0);
+        let path = |values: &[&str]| {
+            values.iter().map(|v| Value::String(v.to_string())).collect::<Vec<_>>()
+        };
+        assert_eq!(view.count, 3);
+        assert_eq!(ids(&view), vec![2, 4]);
+        assert_eq!(view.groups[&path(&["a"])], 0..2);
+        assert_eq!(view.groups[&path(&["a", "x"])], 0..1);
+        assert_eq!(view.groups[&path(&["a", "y"])], 1..2);
+        assert_eq!(view.paths[1], path(&["a", "y"]));
+    }
+
+    #[test]
+    fn unreadable_groups_are_reported_once() {
+        let state = grouped(&["missing"], GroupPagination::Rows);
+        let view = view(&state, None, 0);
+        assert_eq!(ids(&view).len(), 5);
+        assert_eq!(view.groups[&vec![Value::Unit]], 0..5);
+        assert_eq!(view.errors, vec![Error::InvalidFieldName("missing".to_string())]);
+    }
+
+    #[test]
+    fn unreadable_filtered_values_do_not_match_and_are_reported_once() {
+        let mut state = grouped(&[], GroupPagination::Rows);
+        state.columns = vec![ColumnBuilder::new("missing").build()];
+        state.filters = vec![Some(ColumnFilter::Text("x".to_string()))];
+        let view = view(&state, None, 0);
+        assert!(ids(&view).is_empty());
+        assert_eq!(view.errors, vec![Error::InvalidFieldName("missing".to_string())]);
+    }
+
+    #[test]
+    fn duplicate_keys_are_reported_once() {
+        let rows = keyed(&[
+            line(1, None, "a", ""),
+            line(2, None, "b", ""),
+            line(1, None, "c", ""),
+            line(1, None, "d", ""),
+        ]);
+        assert_eq!(duplicate_keys(&rows), vec![Error::DuplicateRowKey("1".to_string())]);
+        assert!(duplicate_keys(&rows[..2]).is_empty());
+    }
+
+    #[test]
+    fn keys_and_indexes_render_differently() {
+        assert_ne!(RowKey::Key("3".to_string()).html_key(), RowKey::Index(3).html_key());
     }
 }
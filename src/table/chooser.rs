@@ -0,0 +1,57 @@
+use super::types::TableData;
+use super::{Callbacks, Options};
+use std::fmt::Debug;
+use yew::{classes, function_component, html, use_context, Html, Properties, UseReducerHandle};
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub label: String,
+}
+
+/// Dropdown listing the columns with a checkbox toggling their visibility.
+#[cfg(feature="table")]
+#[function_component(ColumnChooser)]
+pub fn column_chooser<T>(props: &Props) -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let options = use_context::<Options>().unwrap();
+    let callbacks = use_context::<Callbacks>().unwrap();
+
+    html!(
+        <details class={classes!(options.chooser_classes.clone())}>
+            <summary>{ &props.label }</summary>
+            <ul>
+                {for state.columns.iter().enumerate().map(|(index, column)| {
+                    let onchange = {
+                        let dispatch = state.dispatcher();
+                        let current = state.state.clone();
+                        let on_sort = callbacks.on_sort.clone();
+                        let visible = column.visible;
+                        move |_| {
+                            dispatch.dispatch(super::Msg::ToggleColumn(index));
+                            // Hiding a sorted column removes its sort key.
+                            let mut next = current.clone();
+                            if visible {
+                                next.remove_sort_column(index);
+                            }
+                            if next != current {
+                                on_sort.emit(next);
+                            }
+                        }
+                    };
+                    html! {
+                        <li>
+                            <label>
+                                <input type="checkbox" class={classes!(options.chooser_checkbox_classes.clone())} checked={column.visible} {onchange} />
+                                { format!(" {}", column.name) }
+                            </label>
+                        </li>
+                    }
+                })}
+            </ul>
+        </details>
+    )
+}
@@ -0,0 +1,176 @@
+use super::types::{Editor, EditorContext};
+use super::value::{as_f64, as_string};
+use super::Options;
+use serde_value::Value;
+use web_sys::{HtmlElement, HtmlInputElement, HtmlSelectElement};
+use yew::{
+    classes, function_component, html, use_context, use_effect_with, use_mut_ref, use_node_ref,
+    use_state, Callback, Event, Html, InputEvent, KeyboardEvent, Properties, TargetCast,
+};
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub editor: Editor,
+    pub value: Value,
+    #[prop_or_default]
+    pub validate: Option<Callback<Value, Result<(), String>>>,
+    pub on_commit: Callback<Value>,
+    pub on_cancel: Callback<()>,
+}
+
+/// Editor of a table cell.
+///
+/// Enter and blur commit the value, Escape cancels the edit. A value rejected by the validation
+/// keeps the editor open and shows the error.
+#[cfg(feature="table")]
+#[function_component(CellEditor)]
+pub fn cell_editor(props: &Props) -> Html {
+    let options = use_context::<Options>().unwrap();
+    let draft = use_state(|| match &props.editor {
+        Editor::Checkbox => as_string(&props.value).unwrap_or_else(|| false.to_string()),
+        // The select shows its first choice when the value is not one of them.
+        Editor::Select(choices) => as_string(&props.value)
+            .filter(|value| choices.contains(value))
+            .or_else(|| choices.first().cloned())
+            .unwrap_or_default(),
+        _ => as_string(&props.value).unwrap_or_default(),
+    });
+    let error = use_state(|| None::<String>);
+    let done = use_mut_ref(|| false);
+    let node = use_node_ref();
+
+    {
+        let node = node.clone();
+        use_effect_with((), move |()| {
+            if let Some(element) = node.cast::<HtmlElement>() {
+                let _ = element.focus();
+            }
+        });
+    }
+
+    let commit = {
+        let validate = props.validate.clone();
+        let on_commit = props.on_commit.clone();
+        let error = error.clone();
+        let done = done.clone();
+        Callback::from(move |value: Result<Value, String>| {
+            if *done.borrow() {
+                return;
+            }
+            let value = value.and_then(|value| {
+                validate
+                    .as_ref()
+                    .map_or(Ok(()), |validate| validate.emit(value.clone()))
+                    .map(|()| value)
+            });
+            match value {
+                Ok(value) => {
+                    *done.borrow_mut() = true;
+                    on_commit.emit(value);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    };
+    let cancel = {
+        let on_cancel = props.on_cancel.clone();
+        let done = done.clone();
+        Callback::from(move |()| {
+            *done.borrow_mut() = true;
+            on_cancel.emit(());
+        })
+    };
+    let onkeydown = {
+        let commit = commit.clone();
+        let cancel = cancel.clone();
+        let editor = props.editor.clone();
+        let draft = draft.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => {
+                e.prevent_default();
+                commit.emit(parse(&editor, &draft));
+            }
+            "Escape" => {
+                e.prevent_default();
+                cancel.emit(());
+            }
+            _ => {}
+        })
+    };
+    let onblur = {
+        let commit = commit.clone();
+        let editor = props.editor.clone();
+        let draft = draft.clone();
+        move |_| commit.emit(parse(&editor, &draft))
+    };
+    let input_classes = classes!(options.editor_classes.clone());
+
+    let input = match &props.editor {
+        Editor::Text | Editor::Number => {
+            let input_type = if props.editor == Editor::Number {
+                "number"
+            } else {
+                "text"
+            };
+            let oninput = {
+                let draft = draft.clone();
+                move |e: InputEvent| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    draft.set(input.value());
+                }
+            };
+            html!(<input ref={node} type={input_type} class={input_classes} value={(*draft).clone()} {oninput} {onkeydown} {onblur} />)
+        }
+        Editor::Select(choices) => {
+            let onchange = {
+                let draft = draft.clone();
+                move |e: Event| {
+                    let select: HtmlSelectElement = e.target_unchecked_into();
+                    draft.set(select.value());
+                }
+            };
+            html!(
+                <select ref={node} class={input_classes} {onchange} {onkeydown} {onblur}>
+                    {for choices.iter().map(|choice| html! {
+                        <option value={choice.clone()} selected={*draft == *choice}>{ choice }</option>
+                    })}
+                </select>
+            )
+        }
+        Editor::Checkbox => {
+            let onchange = {
+                let draft = draft.clone();
+                move |e: Event| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    draft.set(input.checked().to_string());
+                }
+            };
+            html!(<input ref={node} type="checkbox" class={input_classes} checked={*draft == "true"} {onchange} {onkeydown} {onblur} />)
+        }
+        Editor::Custom(render) => render.emit(EditorContext {
+            value: props.value.clone(),
+            on_commit: commit.reform(Ok),
+            on_cancel: cancel,
+        }),
+    };
+
+    html!(<>
+        { input }
+        if let Some(error) = (*error).clone() {
+            <div class={classes!(options.editor_error_classes.clone())}>{ error }</div>
+        }
+    </>)
+}
+
+/// Parses the text of the editor into the value of the cell.
+#[cfg(feature="table")]
+fn parse(editor: &Editor, draft: &str) -> Result<Value, String> {
+    match editor {
+        Editor::Number => as_f64(&Value::String(draft.to_string()))
+            .map(Value::F64)
+            .ok_or_else(|| format!("'{draft}' is not a number.")),
+        Editor::Checkbox => Ok(Value::Bool(draft == "true")),
+        _ => Ok(Value::String(draft.to_string())),
+    }
+}
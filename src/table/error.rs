@@ -5,13 +5,17 @@ use std::fmt;
 
 /// Error types
 #[cfg(feature="table")]
-#[derive(Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 #[allow(dead_code)]
 pub enum Error {
     /// Field has no HTML representation defined.
     NonRenderableField(String),
     /// Invalid field name given.
     InvalidFieldName(String),
+    /// Several rows share the same key.
+    DuplicateRowKey(String),
+    /// Field value could not be serialized into a `Value`.
+    NonSerializableField(String),
 }
 
 #[cfg(feature="table")]
@@ -24,6 +28,8 @@ impl fmt::Display for Error {
             Self::NonRenderableField(field_name) => format!(
                 "Could not render field '{field_name}' for which no HTML representation is defined."
             ),
+            Self::DuplicateRowKey(key) => format!("Several rows share the key '{key}'."),
+            Self::NonSerializableField(field) => format!("Could not serialize field {field}."),
         };
         write!(f, "{msg}")
     }
@@ -39,6 +45,8 @@ impl error::Error for Error {
         match self {
             Self::InvalidFieldName(_) => "Invalid field name given.",
             Self::NonRenderableField(_) => "Field has no HTML representation defined.",
+            Self::DuplicateRowKey(_) => "Several rows share the same key.",
+            Self::NonSerializableField(_) => "Field value could not be serialized.",
         }
     }
 }
@@ -0,0 +1,229 @@
+//! Export of the table to CSV, TSV or JSON.
+//!
+//! The Table component offers the export of its visible columns through a toolbar when its
+//! `export` property is set.
+//! The functions of this module give the same output as a `String`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use serde::Serialize;
+//! use yew::Html;
+//! use yew_custom_components::table::error::{Error, Result};
+//! use yew_custom_components::table::export;
+//! use yew_custom_components::table::types::{ColumnBuilder, ExportFormat, TableData};
+//!
+//! #[derive(Clone, Default, PartialEq, PartialOrd, Serialize)]
+//! struct Line {
+//!     name: String,
+//! }
+//!
+//! impl TableData for Line {
+//!     fn get_field_as_html(&self, field_name: &str) -> Result<Html> {
+//!         Ok(Html::from(self.name.clone()))
+//!     }
+//!
+//!     fn get_field_as_value(&self, field_name: &str) -> Result<serde_value::Value> {
+//!         match field_name {
+//!             "name" => Ok(serde_value::Value::String(self.name.clone())),
+//!             _ => Err(Error::InvalidFieldName(field_name.to_string())),
+//!         }
+//!     }
+//!
+//!     fn matches_search(&self, _needle: Option<String>) -> bool {
+//!         true
+//!     }
+//! }
+//!
+//! let columns = vec![ColumnBuilder::new("name").short_name("Name").build()];
+//! let rows = vec![Line { name: "Brown, Fox".to_string() }];
+//! assert_eq!(export::to_string(&columns, &rows, ExportFormat::Csv), "Name\r\n\"Brown, Fox\"\r\n");
+//! assert!(export::to_string(&columns, &rows, ExportFormat::Json).contains("\"name\": \"Brown, Fox\""));
+//! ```
+use super::types::{Column, Export, ExportFormat, ExportScope, TableData};
+use super::value::{as_string, inner};
+use super::Options;
+use serde_value::Value;
+use std::fmt::Debug;
+use std::rc::Rc;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{js_sys, Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+use yew::{classes, function_component, html, use_context, Html, Properties, UseReducerHandle};
+
+/// Serializes the given rows to the format, with one field per column.
+///
+/// CSV and TSV outputs start with a header line made of the column names, JSON outputs are
+/// arrays of objects keyed by the data properties of the columns, as column names may repeat.
+/// Columns sharing a data property are keyed by their name instead.
+/// Fields that cannot be read are left empty.
+#[cfg(feature="table")]
+pub fn to_string<'a, T>(
+    columns: &[Column<T>],
+    rows: impl IntoIterator<Item = &'a T>,
+    format: ExportFormat,
+) -> String
+where
+    T: TableData,
+{
+    let values = rows.into_iter().map(|row| {
+        columns
+            .iter()
+            .map(|c| c.value_of(row).unwrap_or(Value::Unit))
+            .collect::<Vec<_>>()
+    });
+    match format {
+        ExportFormat::Json => {
+            let properties: Vec<&str> = columns
+                .iter()
+                .map(|c| {
+                    let shared = columns.iter().filter(|o| o.property() == c.property()).count() > 1;
+                    if shared { c.name.as_str() } else { c.property() }
+                })
+                .collect();
+            let objects: Vec<serde_json::Map<String, serde_json::Value>> = values
+                .map(|values| {
+                    properties
+                        .iter()
+                        .map(ToString::to_string)
+                        .zip(values.iter().map(|v| serde_json::to_value(v).unwrap_or_default()))
+                        .collect()
+                })
+                .collect();
+            serde_json::to_string_pretty(&objects).unwrap_or_default()
+        }
+        ExportFormat::Csv | ExportFormat::Tsv => {
+            let line = |fields: Vec<String>| {
+                let separator = if format == ExportFormat::Csv { "," } else { "\t" };
+                let fields: Vec<String> = fields.iter().map(|f| escape(f, format)).collect();
+                format!("{}\r\n", fields.join(separator))
+            };
+            std::iter::once(line(columns.iter().map(ToString::to_string).collect()))
+                .chain(values.map(|values| line(values.iter().map(field).collect())))
+                .collect()
+        }
+    }
+}
+
+/// Returns the text of a field, serializing collections as JSON.
+#[cfg(feature="table")]
+fn field(value: &Value) -> String {
+    as_string(value).unwrap_or_else(|| {
+        inner(value)
+            .and_then(|v| serde_json::to_string(v).ok())
+            .unwrap_or_default()
+    })
+}
+
+/// Escapes a field for the separated values formats.
+#[cfg(feature="table")]
+fn escape(field: &str, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv if field.contains([',', '"', '\r', '\n']) => {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        }
+        ExportFormat::Tsv => field.replace(['\t', '\r', '\n'], " "),
+        _ => field.to_string(),
+    }
+}
+
+/// Delay in milliseconds before the URL of a download is revoked, long enough for browsers to
+/// start reading large files.
+#[cfg(feature="table")]
+const REVOKE_DELAY: i32 = 40_000;
+
+/// Offers the content as a file download in the browser.
+#[cfg(feature="table")]
+fn download(file_name: &str, format: ExportFormat, content: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(content));
+    let options = BlobPropertyBag::new();
+    options.set_type(format.mime_type());
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or(JsValue::NULL)?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(&format!("{file_name}.{}", format.extension()));
+    // Some browsers ignore clicks on detached anchors and cancel downloads whose URL is revoked
+    // right away.
+    let body = document.body().ok_or(JsValue::NULL)?;
+    body.append_child(&anchor)?;
+    anchor.click();
+    anchor.remove();
+    let revoke = Closure::once_into_js(move || {
+        let _ = Url::revoke_object_url(&url);
+    });
+    web_sys::window()
+        .ok_or(JsValue::NULL)?
+        .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), REVOKE_DELAY)?;
+    Ok(())
+}
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub(crate) struct Props {
+    pub export: Export,
+}
+
+/// One export button per format.
+#[cfg(feature="table")]
+#[function_component(ExportButtons)]
+pub(crate) fn export_buttons<T>(props: &Props) -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let view = use_context::<Rc<super::body::View<T>>>().unwrap();
+    let callbacks = use_context::<super::Callbacks>().unwrap();
+    let options = use_context::<Options>().unwrap();
+    let export = props.export.clone();
+
+    html!(<>
+        {for export.formats.iter().map(|format| {
+            let format = *format;
+            let onclick = {
+                let state = state.clone();
+                let view = view.clone();
+                let export = export.clone();
+                let on_export = callbacks.on_export.clone();
+                move |_| {
+                    let rows = match export.scope {
+                        ExportScope::All => &view.rows[..],
+                        ExportScope::Page => view.page_rows(),
+                    };
+                    let columns: Vec<Column<T>> = state.visible_columns().map(|(_, c)| c.clone()).collect();
+                    let content = to_string(&columns, rows.iter().map(|(_, row)| row), format);
+                    if let Err(e) = download(&export.file_name, format, &content) {
+                        log::error!("Failed to download the export: {e:?}");
+                    }
+                    on_export.emit((format, content));
+                }
+            };
+            html! {
+                <button type="button" class={classes!(options.export_classes.clone())} {onclick}>{ format.to_string() }</button>
+            }
+        })}
+    </>)
+}
+
+#[cfg(all(test, feature="table"))]
+mod tests {
+    use super::super::tests::{line, Line};
+    use super::super::types::ColumnBuilder;
+    use super::*;
+
+    #[test]
+    fn json_keys_columns_sharing_a_property_by_name() {
+        let columns: Vec<Column<Line>> = vec![
+            ColumnBuilder::new("id").build(),
+            ColumnBuilder::new("name").build(),
+            ColumnBuilder::new("label").data_property("name").build(),
+        ];
+        let json = to_string(&columns, &[line(1, None, "a", "")], ExportFormat::Json);
+        let objects: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(&json).unwrap();
+        let keys: Vec<&str> = objects[0].keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["id", "label", "name"]);
+    }
+}
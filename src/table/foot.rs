@@ -0,0 +1,55 @@
+use super::body::View;
+use super::types::{AggregateScope, TableData};
+use super::Options;
+use serde_value::Value;
+use std::fmt::Debug;
+use std::rc::Rc;
+use yew::{classes, function_component, html, use_context, Html, Properties, UseReducerHandle};
+
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub scope: AggregateScope,
+}
+
+/// Footer row with the aggregates of the columns.
+#[cfg(feature="table")]
+#[function_component(TableFoot)]
+pub fn table_foot<T>(props: &Props) -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let view = use_context::<Rc<View<T>>>().unwrap();
+    let options = use_context::<Options>().unwrap();
+    let rows = match props.scope {
+        AggregateScope::Filtered => &view.rows[..],
+        AggregateScope::Page => view.page_rows(),
+    };
+
+    let frozen = state.frozen_styles(&options);
+    let cell = |position: usize, child: Html| {
+        let style = frozen[position].clone();
+        let class = classes!(style.as_ref().map(|_| options.frozen_classes.clone()));
+        html!(<td {style} {class}>{ child }</td>)
+    };
+    let leading = state.leading_columns();
+
+    html!(
+        <tfoot>
+            <tr class={classes!(options.footer_classes.clone())}>
+                {for (0..leading).map(|position| cell(position, html!()))}
+                {for state.visible_columns().enumerate().map(|(position, (_, column))| {
+                    let Some(aggregate) = &column.aggregate else {
+                        return cell(leading + position, html!());
+                    };
+                    let values: Vec<Value> = rows
+                        .iter()
+                        .filter_map(|(_, row)| column.value_of(row).ok())
+                        .collect();
+                    cell(leading + position, aggregate.render(&values))
+                })}
+            </tr>
+        </tfoot>
+    )
+}
@@ -1,11 +1,21 @@
-use super::types::{Column, TableData, TableState};
+use super::body::View;
+use super::types::{
+    Column, ColumnFilter, ExpandMode, FilterKind, RowKey, SelectionMode, TableData, TableState,
+};
 use super::Options;
 use std::fmt::Debug;
+use std::rc::Rc;
+use web_sys::{Element, HtmlInputElement, HtmlSelectElement};
 use yew::{
-    classes, function_component, html, use_context, Callback, ContextProvider, Html, Properties,
-    UseReducerHandle,
+    classes, function_component, html, use_context, use_mut_ref, Callback, Classes,
+    ContextProvider, DragEvent, Event, Html, InputEvent, KeyboardEvent, MouseEvent, PointerEvent,
+    Properties, TargetCast, UseReducerHandle,
 };
 
+/// Smallest width in pixels a column without `min_width` can be resized to.
+#[cfg(feature="table")]
+const MIN_WIDTH: u32 = 20;
+
 #[cfg(feature="table")]
 #[function_component(TableHead)]
 pub fn table_head<T>() -> Html
@@ -13,41 +23,414 @@ where
     T: TableData + Debug,
 {
     let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let callbacks = use_context::<super::Callbacks>().unwrap();
+    let options = use_context::<Options>().unwrap();
+    let frozen = state.frozen_styles(&options);
     let sort_state = state.state.clone();
     let orderable = state.orderable;
     let columns = state.columns.clone();
+    let handle_move = {
+        let dispatch = state.dispatcher().clone();
+        let current = sort_state.clone();
+        let callbacks = callbacks.clone();
+        Callback::from(move |(from, to): (usize, usize)| {
+            if from == to {
+                return;
+            }
+            dispatch.dispatch(super::Msg::MoveColumn(from, to));
+            // The indexes of the sort state follow the columns, which the parent may have saved.
+            let mut next = current.clone();
+            next.move_column(from, to);
+            if next != current {
+                callbacks.on_sort.emit(next);
+            }
+        })
+    };
+    let dragged = use_mut_ref(|| None::<usize>);
+    let on_drag_start = {
+        let dragged = dragged.clone();
+        Callback::from(move |index: usize| *dragged.borrow_mut() = Some(index))
+    };
+    let on_drop = {
+        let handle_move = handle_move.clone();
+        Callback::from(move |index: usize| {
+            if let Some(from) = dragged.borrow_mut().take() {
+                handle_move.emit((from, index));
+            }
+        })
+    };
+    let visible: Vec<usize> = state.visible_columns().map(|(index, _)| index).collect();
+    let reorderable = state.reorderable;
+    let resizable = state.resizable;
+    let handle_resize = {
+        let dispatch = state.dispatcher().clone();
+        let columns = columns.clone();
+        let on_column_resize = callbacks.on_column_resize.clone();
+        Callback::from(move |(index, width, done): (usize, u32, bool)| {
+            dispatch.dispatch(super::Msg::ResizeColumn(index, width));
+            if let Some(column) = columns.get(index).filter(|_| done) {
+                on_column_resize.emit((column.name.clone(), width));
+            }
+        })
+    };
     let handle_sort = {
         let dispatch = state.dispatcher().clone();
-        Callback::from(move |index: usize| dispatch.dispatch(super::Msg::SortColumn(index)))
+        let current = sort_state.clone();
+        Callback::from(move |(index, additive): (usize, bool)| {
+            let mut next = current.clone();
+            if additive {
+                next.add_sort_column(index);
+            } else {
+                next.sort_column(index);
+            }
+            if !callbacks.sort_controlled {
+                dispatch.dispatch(super::Msg::SetState(next.clone()));
+            }
+            callbacks.on_sort.emit(next);
+        })
+    };
+
+    let leading = state.leading_columns();
+    let grouped = visible.iter().any(|index| columns[*index].group.is_some());
+    let rowspan = if grouped { 2 } else { 1 };
+    let head_column = |position: usize, rowspan: usize| {
+        let index = visible[position];
+        let neighbours = (
+            position.checked_sub(1).map(|p| visible[p]),
+            visible.get(position + 1).copied(),
+        );
+        html! {
+            <HeadColumn<T> column={columns[index].clone()} {orderable} callback={handle_sort.clone()} {index} {rowspan} frozen={frozen[leading + position].clone()}
+                {reorderable} {neighbours} on_drag_start={on_drag_start.clone()} on_drop={on_drop.clone()} on_move={handle_move.clone()}
+                {resizable} on_resize={handle_resize.clone()} />
+        }
     };
 
+    // Consecutive visible columns of the same group share a header spanning them, ungrouped
+    // columns span both header rows.
+    let mut top = vec![];
+    let mut bottom = vec![];
+    let mut position = 0;
+    while position < visible.len() {
+        let group = columns[visible[position]].group.clone();
+        let run = visible[position..]
+            .iter()
+            .take_while(|index| columns[**index].group == group)
+            .count();
+        match group {
+            Some(name) => {
+                // A group of frozen columns sticks along with them, at the offset of its outer child.
+                let children = &frozen[leading + position..leading + position + run];
+                let style = if children.iter().all(Option::is_some) {
+                    children[0].clone().filter(|style| style.contains("left:")).or_else(|| children[run - 1].clone())
+                } else {
+                    None
+                };
+                let class = classes!(options.column_group_classes.clone(), style.as_ref().map(|_| options.frozen_classes.clone()));
+                top.push(html! {
+                    <th scope="colgroup" colspan={run.to_string()} {style} {class}>{ name }</th>
+                });
+                bottom.extend((position..position + run).map(|p| head_column(p, 1)));
+            }
+            None => top.extend((position..position + run).map(|p| head_column(p, rowspan))),
+        }
+        position += run;
+    }
+
     html!(
-        <thead>
+        <thead style={options.sticky_header.then_some("position: sticky; top: 0; z-index: 2;")}
+            class={classes!(options.sticky_header.then(|| options.frozen_classes.clone()))}>
             <ContextProvider<TableState> context={sort_state}>
-                {for columns.iter().enumerate().map(|(index, column)| {
-                    html! {
-                        <HeadColumn column={column.clone()} {orderable} callback={handle_sort.clone()} {index}/>
+                <tr>
+                    if state.expand != ExpandMode::None {
+                        <ExpandAll<T> {rowspan} />
+                    }
+                    if state.selection != SelectionMode::None {
+                        <SelectAll<T> {rowspan} />
                     }
-                })}
+                    {for top}
+                </tr>
+                if grouped {
+                    <tr>{for bottom}</tr>
+                }
             </ContextProvider<TableState>>
+            if state.filterable {
+                <FilterRow<T> />
+            }
         </thead>
     )
 }
 
+#[cfg(feature="table")]
+#[derive(Properties, Clone, PartialEq)]
+pub struct LeadingProps {
+    #[prop_or(1)]
+    pub rowspan: usize,
+}
+
+#[cfg(feature="table")]
+#[function_component(ExpandAll)]
+pub fn expand_all<T>(props: &LeadingProps) -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let view = use_context::<Rc<View<T>>>().unwrap();
+    let options = use_context::<Options>().unwrap();
+
+    let frozen = state.frozen_styles(&options).first().cloned().flatten();
+    let class = classes!(frozen.as_ref().map(|_| options.frozen_classes.clone()));
+
+    if state.expand != ExpandMode::Multiple {
+        return html!(<th scope="col" rowspan={props.rowspan.to_string()} style={frozen} {class}></th>);
+    }
+
+    let expanded = !state.expanded.is_empty();
+    let onclick = {
+        let dispatch = state.dispatcher();
+        let keys: Vec<RowKey> = if expanded {
+            state.expanded.iter().cloned().collect()
+        } else {
+            view.rows
+                .iter()
+                .filter(|(_, row)| row.render_details().is_some())
+                .map(|(key, _)| key.clone())
+                .collect()
+        };
+        move |_| dispatch.dispatch(super::Msg::SetRowsExpanded(keys.clone(), !expanded))
+    };
+    let toggle_class = if expanded {
+        options.expanded_class.clone()
+    } else {
+        options.collapsed_class.clone()
+    };
+
+    html!(
+        <th scope="col" rowspan={props.rowspan.to_string()} role="button" style={frozen} {class} {onclick}>
+            <i class={classes!(options.toggle_classes.clone(), toggle_class)}></i>
+        </th>
+    )
+}
+
+#[cfg(feature="table")]
+#[function_component(SelectAll)]
+pub fn select_all<T>(props: &LeadingProps) -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let view = use_context::<Rc<View<T>>>().unwrap();
+    let options = use_context::<Options>().unwrap();
+
+    let position = usize::from(state.expand != ExpandMode::None);
+    let frozen = state.frozen_styles(&options).get(position).cloned().flatten();
+    let class = classes!(frozen.as_ref().map(|_| options.frozen_classes.clone()));
+
+    if state.selection != SelectionMode::Multi {
+        return html!(<th scope="col" rowspan={props.rowspan.to_string()} style={frozen} {class}></th>);
+    }
+
+    let keys: Vec<RowKey> = view.page_rows().iter().map(|(key, _)| key.clone()).collect();
+    let checked = !keys.is_empty() && keys.iter().all(|key| state.selected.contains(key));
+    let onclick = {
+        let dispatch = state.dispatcher();
+        move |_| dispatch.dispatch(super::Msg::SetRowsSelected(keys.clone(), !checked))
+    };
+
+    html!(
+        <th scope="col" rowspan={props.rowspan.to_string()} style={frozen} {class}>
+            <input type="checkbox" class={classes!(options.selection_classes.clone())} {checked} {onclick} />
+        </th>
+    )
+}
+
+#[cfg(feature="table")]
+#[function_component(FilterRow)]
+pub fn filter_row<T>() -> Html
+where
+    T: TableData + Debug,
+{
+    let state = use_context::<UseReducerHandle<super::Data<T>>>().unwrap();
+    let options = use_context::<Options>().unwrap();
+    let classes = classes!(options.filter_classes.clone());
+    let frozen = state.frozen_styles(&options);
+    let cell = |position: usize, child: Html| {
+        let style = frozen[position].clone();
+        let class = classes!(style.as_ref().map(|_| options.frozen_classes.clone()));
+        html!(<th {style} {class}>{ child }</th>)
+    };
+    let leading = state.leading_columns();
+
+    html!(
+        <tr>
+            {for (0..leading).map(|position| cell(position, html!()))}
+            {for state.visible_columns().enumerate().map(|(position, (index, column))| {
+                let current = state.filters.get(index).cloned().flatten();
+                let callback = {
+                    let dispatch = state.dispatcher();
+                    Callback::from(move |filter: Option<ColumnFilter>| {
+                        dispatch.dispatch(super::Msg::SetFilter(index, filter));
+                    })
+                };
+                cell(leading + position, html! {
+                    if let Some(kind) = column.filter.as_ref() {
+                        { filter_input(kind, current, &callback, &classes) }
+                    }
+                })
+            })}
+        </tr>
+    )
+}
+
+/// Renders the input of a column filter.
+#[cfg(feature="table")]
+fn filter_input(
+    kind: &FilterKind,
+    current: Option<ColumnFilter>,
+    callback: &Callback<Option<ColumnFilter>>,
+    classes: &Classes,
+) -> Html {
+    let non_empty = |value: String| if value.is_empty() { None } else { Some(value) };
+
+    match kind {
+        FilterKind::Text => {
+            let value = match current {
+                Some(ColumnFilter::Text(text)) => text,
+                _ => String::new(),
+            };
+            let oninput = callback.reform(move |e: InputEvent| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                non_empty(input.value()).map(ColumnFilter::Text)
+            });
+            html!(<input type="text" class={classes.clone()} {value} {oninput} />)
+        }
+        FilterKind::NumberRange => {
+            let (min, max) = match current {
+                Some(ColumnFilter::NumberRange { min, max }) => (min, max),
+                _ => (None, None),
+            };
+            let range = |min: Option<f64>, max: Option<f64>| {
+                (min.is_some() || max.is_some()).then_some(ColumnFilter::NumberRange { min, max })
+            };
+            let oninput_min = callback.reform(move |e: InputEvent| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                range(input.value().parse().ok(), max)
+            });
+            let oninput_max = callback.reform(move |e: InputEvent| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                range(min, input.value().parse().ok())
+            });
+            html!(<>
+                <input type="number" class={classes.clone()} placeholder="min" value={min.map(|v| v.to_string()).unwrap_or_default()} oninput={oninput_min} />
+                <input type="number" class={classes.clone()} placeholder="max" value={max.map(|v| v.to_string()).unwrap_or_default()} oninput={oninput_max} />
+            </>)
+        }
+        FilterKind::Select(choices) => {
+            let selected = match current {
+                Some(ColumnFilter::Select(option)) => Some(option),
+                _ => None,
+            };
+            let onchange = callback.reform(move |e: Event| {
+                let select: HtmlSelectElement = e.target_unchecked_into();
+                non_empty(select.value()).map(ColumnFilter::Select)
+            });
+            html!(
+                <select class={classes.clone()} {onchange}>
+                    <option value="" selected={selected.is_none()}></option>
+                    {for choices.iter().map(|choice| html! {
+                        <option value={choice.clone()} selected={selected.as_ref() == Some(choice)}>{ choice }</option>
+                    })}
+                </select>
+            )
+        }
+        FilterKind::Boolean => {
+            let selected = match current {
+                Some(ColumnFilter::Boolean(value)) => Some(value),
+                _ => None,
+            };
+            let onchange = callback.reform(move |e: Event| {
+                let select: HtmlSelectElement = e.target_unchecked_into();
+                select.value().parse().ok().map(ColumnFilter::Boolean)
+            });
+            html!(
+                <select class={classes.clone()} {onchange}>
+                    <option value="" selected={selected.is_none()}></option>
+                    <option value="true" selected={selected == Some(true)}>{ "true" }</option>
+                    <option value="false" selected={selected == Some(false)}>{ "false" }</option>
+                </select>
+            )
+        }
+        FilterKind::DateRange => {
+            let (from, to) = match current {
+                Some(ColumnFilter::DateRange { from, to }) => (from, to),
+                _ => (None, None),
+            };
+            let range = |from: Option<String>, to: Option<String>| {
+                (from.is_some() || to.is_some()).then_some(ColumnFilter::DateRange { from, to })
+            };
+            let oninput_from = {
+                let to = to.clone();
+                callback.reform(move |e: InputEvent| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    range(non_empty(input.value()), to.clone())
+                })
+            };
+            let oninput_to = {
+                let from = from.clone();
+                callback.reform(move |e: InputEvent| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    range(from.clone(), non_empty(input.value()))
+                })
+            };
+            html!(<>
+                <input type="date" class={classes.clone()} value={from.unwrap_or_default()} oninput={oninput_from} />
+                <input type="date" class={classes.clone()} value={to.unwrap_or_default()} oninput={oninput_to} />
+            </>)
+        }
+    }
+}
+
 #[cfg(feature="table")]
 #[derive(Properties, Clone, PartialEq, Default)]
-pub struct ColumnProps {
+pub struct ColumnProps<T>
+where
+    T: TableData,
+{
     index: usize,
-    callback: Callback<usize>,
+    callback: Callback<(usize, bool)>,
     #[prop_or(false)]
     pub orderable: bool,
     #[prop_or_default]
-    pub column: Column,
+    pub column: Column<T>,
+    #[prop_or(false)]
+    pub reorderable: bool,
+    /// Index of the previous and next visible columns, targets of the keyboard moves.
+    #[prop_or_default]
+    pub neighbours: (Option<usize>, Option<usize>),
+    #[prop_or_default]
+    pub on_drag_start: Callback<usize>,
+    #[prop_or_default]
+    pub on_drop: Callback<usize>,
+    #[prop_or_default]
+    pub on_move: Callback<(usize, usize)>,
+    #[prop_or(1)]
+    pub rowspan: usize,
+    /// Sticky style of the header when the column is frozen.
+    #[prop_or_default]
+    pub frozen: Option<String>,
+    #[prop_or(false)]
+    pub resizable: bool,
+    /// Called with the index, the new width and whether the resize is over.
+    #[prop_or_default]
+    pub on_resize: Callback<(usize, u32, bool)>,
 }
 
 #[cfg(feature="table")]
 #[function_component(HeadColumn)]
-pub fn head_column(props: &ColumnProps) -> Html {
+pub fn head_column<T>(props: &ColumnProps<T>) -> Html
+where
+    T: TableData,
+{
     let column = &props.column;
     let state = use_context::<TableState>().unwrap();
     let options = use_context::<Options>().unwrap();
@@ -64,19 +447,116 @@ pub fn head_column(props: &ColumnProps) -> Html {
         })
     };
 
-    let th_view = |child| {
-        if props.orderable && column.orderable {
-            html!( <th class={classes!(column.header_classes.clone())} scope="col" onclick={move |_| { cb.emit(index) }}>{ child }</th> )
-        } else {
-            html!( <th class={classes!(column.header_classes.clone())} scope="col">{ child }</th> )
+    let priority = if state.sort_keys().len() > 1 {
+        state.priority_of(index)
+    } else {
+        None
+    };
+
+    let onclick = (props.orderable && column.orderable)
+        .then(|| Callback::from(move |e: MouseEvent| cb.emit((index, e.shift_key()))));
+    let reorder = |callback: Callback<DragEvent>| props.reorderable.then_some(callback);
+    let resize = use_mut_ref(|| None::<(i32, i32)>);
+    let ondragstart = reorder({
+        let on_drag_start = props.on_drag_start.clone();
+        let resize = resize.clone();
+        Callback::from(move |e: DragEvent| {
+            if resize.borrow().is_some() {
+                e.prevent_default();
+                return;
+            }
+            if let Some(transfer) = e.data_transfer() {
+                transfer.set_effect_allowed("move");
+                let _ = transfer.set_data("text/plain", &index.to_string());
+            }
+            on_drag_start.emit(index);
+        })
+    });
+    let ondragover = reorder(Callback::from(|e: DragEvent| e.prevent_default()));
+    let ondrop = reorder({
+        let on_drop = props.on_drop.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            on_drop.emit(index);
+        })
+    });
+    let onkeydown = props.reorderable.then(|| {
+        let on_move = props.on_move.clone();
+        let (previous, next) = props.neighbours;
+        Callback::from(move |e: KeyboardEvent| {
+            let target = match e.key().as_str() {
+                "ArrowLeft" if e.alt_key() => previous,
+                "ArrowRight" if e.alt_key() => next,
+                _ => None,
+            };
+            if let Some(target) = target {
+                e.prevent_default();
+                on_move.emit((index, target));
+            }
+        })
+    });
+    let width = {
+        let resize = resize.clone();
+        let (min, max) = (column.min_width.unwrap_or(MIN_WIDTH), column.max_width.unwrap_or(u32::MAX));
+        move |e: &PointerEvent| {
+            resize.borrow().map(|(x, width)| {
+                u32::try_from(width + e.client_x() - x).unwrap_or(0).clamp(min, max.max(min))
+            })
+        }
+    };
+    let onpointerdown = {
+        let resize = resize.clone();
+        move |e: PointerEvent| {
+            e.stop_propagation();
+            let handle: Element = e.target_unchecked_into();
+            let _ = handle.set_pointer_capture(e.pointer_id());
+            let width = handle.parent_element().map_or(0, |th| th.client_width());
+            *resize.borrow_mut() = Some((e.client_x(), width));
+        }
+    };
+    let onpointermove = {
+        let width = width.clone();
+        let on_resize = props.on_resize.clone();
+        move |e: PointerEvent| {
+            if let Some(width) = width(&e) {
+                on_resize.emit((index, width, false));
+            }
         }
     };
+    let onpointerup = {
+        let on_resize = props.on_resize.clone();
+        move |e: PointerEvent| {
+            if let Some(width) = width(&e) {
+                on_resize.emit((index, width, true));
+            }
+            *resize.borrow_mut() = None;
+        }
+    };
+    let th_view = |child| {
+        html!(
+            <th class={classes!(column.header_classes.clone(), props.frozen.as_ref().map(|_| options.frozen_classes.clone()))} scope="col" rowspan={props.rowspan.to_string()} {onclick}
+                style={props.frozen.clone().or_else(|| props.resizable.then(|| "position: relative;".to_string()))}
+                draggable={props.reorderable.then_some("true")} tabindex={props.reorderable.then_some("0")}
+                {ondragstart} {ondragover} {ondrop} {onkeydown}>
+                { child }
+                if props.resizable {
+                    <span class={classes!(options.resize_classes.clone())}
+                        style="position: absolute; top: 0; right: 0; bottom: 0; width: 6px; cursor: col-resize; touch-action: none;"
+                        onclick={|e: MouseEvent| e.stop_propagation()}
+                        {onpointerdown} {onpointermove} {onpointerup}></span>
+                }
+            </th>
+        )
+    };
 
     th_view(html!(
         <span>
             { column }
             if props.orderable && column.orderable {
                 <i class={classes!(options.orderable_classes.clone(), get_header_sorting_class(index))}></i>
+                if let Some(priority) = priority {
+                    <sup class={classes!(options.priority_classes.clone())}>{ priority }</sup>
+                }
             }
         </span>
     ))
@@ -3,49 +3,57 @@
 /// # Example
 ///
 /// ```rust
-/// let columns = vec![
-///    column!("date", "Date"),
-///   column!("type", "Type"),
-///  column!("amount", "Amount"),
+/// use yew_custom_components::column;
+/// use yew_custom_components::table::types::Column;
+///
+/// # struct Line;
+/// let columns: Vec<Column<Line>> = vec![
+///     column!("date", "Date"),
+///     column!("type", "Type"),
+///     column!("amount", "Amount"),
 /// ];
 /// ```
 #[cfg(feature="table")]
 #[macro_export]
 macro_rules! column {
     ($a:expr) => {{
-        $crate::components::table::types::Column {
+        $crate::table::types::Column {
             data_property: Some($a.to_string()),
             name: $a.to_string(),
             short_name: Some($a.to_string()),
             orderable: false,
             header_classes: vec![],
+            ..Default::default()
         }
     }};
     ($a:expr, $b:expr) => {{
-        $crate::components::table::types::Column {
+        $crate::table::types::Column {
             data_property: Some($a.to_string()),
             name: $b.to_string(),
             short_name: Some($b.to_string()),
             orderable: false,
             header_classes: vec![],
+            ..Default::default()
         }
     }};
     ($a:expr, $b:expr, $c:expr) => {
-        $crate::components::table::types::Column {
+        $crate::table::types::Column {
             data_property: Some($a.to_string()),
             name: $b.to_string(),
             short_name: Some($c.to_string()),
             orderable: false,
             header_classes: vec![],
+            ..Default::default()
         }
     };
     ($a:expr, $b:expr, $c:expr, $d:expr) => {
-        $crate::components::table::types::Column {
+        $crate::table::types::Column {
             data_property: Some($a.to_string()),
             name: $b.to_string(),
             short_name: Some($c.to_string()),
             orderable: $d,
             header_classes: vec![],
+            ..Default::default()
         }
     };
 }
@@ -1,18 +1,62 @@
 //! Table component.
 //!
 //! This component is a table that can be sorted by columns.
+//! Shift-clicking a column header adds it to the sort keys, so rows tie-break by the
+//! following columns in the order they were added.
+use std::collections::BTreeSet;
 use std::fmt::Debug;
-use types::{Column, TableData, TableOrder, TableState};
+use std::rc::Rc;
+use types::{
+    AggregateScope, Column, ColumnFilter, ExpandMode, Export, ExportFormat, GroupPagination, Query, RowKey, SelectionMode, TableData, TableOrder,
+    TableState,
+};
+use serde_value::Value;
 use yew::html;
 use yew::prelude::*;
 
 mod body;
+mod chooser;
+mod editor;
 pub mod error;
+pub mod export;
+mod foot;
 mod head;
 mod macros;
 pub mod types;
+mod value;
+
+/// Re-exports used by the code generated by the derive macros.
+#[cfg(feature="derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use serde_value;
+}
+
+/// Width of the selection and toggle columns when columns are frozen.
+#[cfg(feature="table")]
+const LEADING_WIDTH: &str = "2.5rem";
+
+/// Width in pixels of a frozen column without `width` nor `min_width`.
+#[cfg(feature="table")]
+const FROZEN_WIDTH: u32 = 100;
+
+/// Number of skeleton rows rendered while loading when the table has no limit.
+#[cfg(feature="table")]
+const SKELETON_ROWS: usize = 3;
 
 /// Options for the Table component.
+///
+/// New options are added as the table gains features, so build the options with
+/// `..Default::default()` to keep compiling across releases:
+///
+/// ```rust
+/// use yew_custom_components::table::Options;
+///
+/// let options = Options {
+///     sticky_header: true,
+///     ..Default::default()
+/// };
+/// ```
 #[cfg(feature="table")]
 #[derive(Clone, Eq, PartialEq, Default)]
 pub struct Options {
@@ -24,22 +68,94 @@ pub struct Options {
     pub descending_class: Option<String>,
     /// Css classes for the orderable state.
     pub orderable_classes: Vec<String>,
+    /// Css classes for the sort priority number shown when sorting by multiple columns.
+    pub priority_classes: Vec<String>,
+    /// Css classes for the inputs of the filter row.
+    pub filter_classes: Vec<String>,
+    /// Css classes for the checkboxes of the selection column.
+    pub selection_classes: Vec<String>,
+    /// Css classes for the detail row toggles.
+    pub toggle_classes: Vec<String>,
+    /// Css classes for the expanded state of a toggle.
+    pub expanded_class: Option<String>,
+    /// Css classes for the collapsed state of a toggle.
+    pub collapsed_class: Option<String>,
+    /// Css classes for the inputs of the cell editors.
+    pub editor_classes: Vec<String>,
+    /// Css classes for the validation error of a cell editor.
+    pub editor_error_classes: Vec<String>,
+    /// Css classes for the toolbar above the table.
+    pub toolbar_classes: Vec<String>,
+    /// Css classes for the export buttons.
+    pub export_classes: Vec<String>,
+    /// Css classes for the footer row of aggregates.
+    pub footer_classes: Vec<String>,
+    /// Css classes for the group header rows.
+    pub group_classes: Vec<String>,
+    /// Css classes for the column chooser.
+    pub chooser_classes: Vec<String>,
+    /// Css classes for the checkboxes of the column chooser.
+    pub chooser_checkbox_classes: Vec<String>,
+    /// Css classes for the resize handles of the column headers.
+    pub resize_classes: Vec<String>,
+    /// Whether the header sticks to the top of the scroll container.
+    ///
+    /// Frozen columns put the table in a horizontal scroll container, which the header cannot
+    /// stick out of: set `max_height` so that the header sticks to the top of that container.
+    pub sticky_header: bool,
+    /// Css height the table is bounded to, scrolling inside its container beyond it, e.g. `"60vh"`.
+    ///
+    /// Ignored with `virtual_scroll`, which sets its own height.
+    pub max_height: Option<String>,
+    /// Number of visible columns frozen on the left, along with the selection and toggle columns.
+    ///
+    /// Frozen columns are offset by the widths of the frozen columns before them, taken from
+    /// `Column::width`, then `Column::min_width`, then 100 pixels.
+    pub frozen_left: usize,
+    /// Number of visible columns frozen on the right.
+    pub frozen_right: usize,
+    /// Css classes for the sticky header and the frozen cells, which need an opaque background.
+    pub frozen_classes: Vec<String>,
+    /// Css classes for the headers of the column groups.
+    pub column_group_classes: Vec<String>,
+    /// Text rendered in the cells whose Html cannot be read from the data.
+    pub error_placeholder: String,
+    /// Css classes for the placeholders of the skeleton rows rendered while loading.
+    pub skeleton_classes: Vec<String>,
+    /// Css classes for the cell holding the empty and error states.
+    pub message_classes: Vec<String>,
 }
 
 /// Properties of the Table component.
 #[cfg(feature="table")]
-#[derive(Properties, Clone, Eq, PartialEq, Default)]
+#[derive(Properties, Clone, PartialEq, Default)]
 pub struct Props<T>
 where
     T: TableData + Debug,
 {
     /// Columns of the table.
-    pub columns: Vec<Column>,
+    pub columns: Vec<Column<T>>,
     /// Table data
     pub data: Vec<T>,
     /// Whether the table is orderable.
     #[prop_or(false)]
     pub orderable: bool,
+    /// Whether the filter row is rendered under the header.
+    #[prop_or(false)]
+    pub filterable: bool,
+    /// Whether the columns can be reordered by dragging their header, or with Alt+Left and
+    /// Alt+Right on a focused header.
+    #[prop_or(false)]
+    pub reorderable: bool,
+    /// Whether the columns can be resized by dragging the right edge of their header.
+    ///
+    /// The initial widths are taken from `Column::width`. The table then has a fixed layout, so
+    /// that the widths apply exactly instead of being stretched to the content of the cells.
+    #[prop_or(false)]
+    pub resizable: bool,
+    /// Called with the name and the width in pixels of a column whenever the user resizes it.
+    #[prop_or_default]
+    pub on_column_resize: Callback<(String, u32)>,
     /// Css classes for the table.
     #[prop_or_default]
     pub classes: Classes,
@@ -55,6 +171,98 @@ where
     /// Page for pagination
     #[prop_or(0)]
     pub page: usize,
+    /// Called with the number of rows matching the search, before pagination is applied.
+    ///
+    /// When the pagination counts groups, called with the number of top-level groups instead.
+    #[prop_or_default]
+    pub on_filtered_count: Callback<usize>,
+    /// Sort state controlled by the parent. When set, the table follows it and sorting
+    /// only emits `on_sort`.
+    #[prop_or_default]
+    pub sort: Option<TableState>,
+    /// Called with the new sort state whenever the user changes the sorting, including when
+    /// a sorted column is moved or hidden.
+    #[prop_or_default]
+    pub on_sort: Callback<TableState>,
+    /// Whether the data is sorted, filtered and paginated by the parent.
+    ///
+    /// The table then renders `data` as given and emits `on_query` whenever the rows to display change.
+    #[prop_or(false)]
+    pub remote: bool,
+    /// Called with the query for the rows to display when the table is remote.
+    #[prop_or_default]
+    pub on_query: Callback<Query>,
+    /// Total number of rows matching the query when the table is remote.
+    #[prop_or_default]
+    pub total: Option<usize>,
+    /// Renders only the rows in view inside a scroll container when set.
+    #[prop_or_default]
+    pub virtual_scroll: Option<VirtualScroll>,
+    /// Row selection mode. Adds a selection column unless `SelectionMode::None`.
+    #[prop_or_default]
+    pub selection: SelectionMode,
+    /// Called with the keys of the selected rows whenever the selection changes.
+    #[prop_or_default]
+    pub on_selection_change: Callback<Vec<RowKey>>,
+    /// Detail row expansion mode. Adds a toggle column unless `ExpandMode::None`.
+    ///
+    /// Details are rendered by `TableData::render_details`.
+    #[prop_or_default]
+    pub expand: ExpandMode,
+    /// Called with the row key, the data property and the new value whenever a cell edit is committed.
+    ///
+    /// The table does not change its data, the parent is expected to update `data`.
+    #[prop_or_default]
+    pub on_cell_edit: Callback<(RowKey, String, Value)>,
+    /// Renders a toolbar with export buttons above the table when set.
+    #[prop_or_default]
+    pub export: Option<Export>,
+    /// Called with the format and the content of every export made from the toolbar.
+    #[prop_or_default]
+    pub on_export: Callback<(ExportFormat, String)>,
+    /// Rows the aggregates of the columns are computed over.
+    ///
+    /// The footer is rendered when at least one column has an aggregate.
+    #[prop_or_default]
+    pub aggregate_scope: AggregateScope,
+    /// Data properties the rows are grouped by, from the outermost group to the innermost.
+    ///
+    /// Groups are sorted by their value and rows are sorted inside their group. Grouping is not
+    /// applied to remote tables, and grouped tables are not virtually scrolled.
+    /// `TableFields::field_names` builds it from typed fields.
+    #[prop_or_default]
+    pub group_by: Vec<String>,
+    /// Unit counted by the pagination when the rows are grouped.
+    #[prop_or_default]
+    pub group_pagination: GroupPagination,
+    /// Whether the group header rows render the aggregates of the columns over the rows of the group.
+    #[prop_or(false)]
+    pub group_aggregates: bool,
+    /// Whether the rows form a tree through `TableData::parent_key`.
+    ///
+    /// The first column is indented and gets a toggle on rows with children. Siblings are sorted
+    /// among themselves and the search and filters keep the ancestors of the matching rows.
+    /// Trees are not grouped and are not built for remote tables.
+    #[prop_or(false)]
+    pub tree: bool,
+    /// Renders a column chooser with the given label in the toolbar when set.
+    #[prop_or_default]
+    pub column_chooser: Option<String>,
+    /// Called with the columns whenever their visibility or their order changes.
+    #[prop_or_default]
+    pub on_columns_change: Callback<Vec<Column<T>>>,
+    /// Called with the errors met while reading the data, which are logged as well.
+    #[prop_or_default]
+    pub on_error: Callback<error::Error>,
+    /// Whether the data is loading, in which case skeleton rows replace the rows.
+    #[prop_or(false)]
+    pub loading: bool,
+    /// Rendered across the columns when no row matches the search and the filters.
+    #[prop_or_default]
+    pub empty: Html,
+    /// Rendered across the columns instead of the rows when set.
+    #[prop_or_default]
+    pub error: Option<Html>,
 }
 
 /// Messages for the Table component.
@@ -64,23 +272,300 @@ pub enum Msg<T>
 where
     T: TableData + Debug,
 {
-    /// Sort a column.
-    SortColumn(usize),
     /// Set the data of the table.
     SetData(Vec<T>),
+    /// Set or clear the filter of a column.
+    SetFilter(usize, Option<ColumnFilter>),
+    /// Set the sort state of the table.
+    SetState(TableState),
+    /// Toggle the selection of a row.
+    ToggleRow(RowKey),
+    /// Select or deselect the given rows.
+    SetRowsSelected(Vec<RowKey>, bool),
+    /// Toggle the detail row of a row.
+    ToggleDetails(RowKey),
+    /// Expand or collapse the detail rows of the given rows.
+    SetRowsExpanded(Vec<RowKey>, bool),
+    /// Set the data properties the rows are grouped by and the unit counted by the pagination.
+    SetGrouping(Vec<String>, GroupPagination),
+    /// Collapse or expand a group given the values of its path.
+    ToggleGroup(Vec<Value>),
+    /// Collapse or expand the children of a row of a tree.
+    ToggleNode(RowKey),
+    /// Show or hide a column.
+    ToggleColumn(usize),
+    /// Move a column to another index, shifting the columns in between.
+    MoveColumn(usize, usize),
+    /// Set the width of a column in pixels.
+    ResizeColumn(usize, u32),
+    /// Set the columns given by the properties, keeping the layout set by the user.
+    SetColumns(Vec<Column<T>>),
+    /// Set the modes given by the properties.
+    SetModes(Modes),
+}
+
+/// Modes of the table given by its properties.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Modes {
+    /// Whether the table is orderable.
+    pub orderable: bool,
+    /// Whether the table renders a filter row.
+    pub filterable: bool,
+    /// Whether the columns can be reordered.
+    pub reorderable: bool,
+    /// Whether the columns can be resized.
+    pub resizable: bool,
+    /// Whether the data is sorted, filtered and paginated by the parent.
+    pub remote: bool,
+    /// Row selection mode.
+    pub selection: SelectionMode,
+    /// Detail row expansion mode.
+    pub expand: ExpandMode,
+    /// Whether the rows form a tree.
+    pub tree: bool,
 }
 
 /// Data for the Table component.
 #[cfg(feature="table")]
-#[derive(Clone, Eq, PartialEq, Default)]
+#[derive(Clone, PartialEq, Default)]
 pub struct Data<T>
 where
     T: TableData + Debug,
 {
-    columns: Vec<Column>,
-    data: Vec<T>,
+    columns: Vec<Column<T>>,
+    /// Columns as last given by the properties, telling their changes from the user's.
+    declared: Vec<Column<T>>,
+    /// Rows given by the properties, shared so that updating the rest of the state is cheap.
+    data: Rc<Vec<T>>,
     orderable: bool,
+    reorderable: bool,
+    resizable: bool,
+    filterable: bool,
+    remote: bool,
     state: TableState,
+    filters: Vec<Option<ColumnFilter>>,
+    selection: SelectionMode,
+    selected: BTreeSet<RowKey>,
+    anchor: Option<RowKey>,
+    expand: ExpandMode,
+    expanded: BTreeSet<RowKey>,
+    group_by: Vec<String>,
+    group_pagination: GroupPagination,
+    collapsed: BTreeSet<Vec<Value>>,
+    tree: bool,
+    collapsed_nodes: BTreeSet<RowKey>,
+}
+
+#[cfg(feature="table")]
+impl<T> Data<T>
+where
+    T: TableData + Debug,
+{
+    /// Returns the sort keys as data property and order pairs.
+    fn sort_keys(&self) -> Vec<(String, TableOrder)> {
+        self.sort_columns()
+            .into_iter()
+            .map(|(column, order)| (column.property().to_string(), order))
+            .collect()
+    }
+
+    /// Replaces the columns by the ones given by the properties.
+    ///
+    /// Columns are matched by name. The order of the columns set by the user is kept unless the
+    /// order given by the properties changes, as are their widths and visibility unless the
+    /// properties change them. The filters and the sort state follow their columns.
+    fn set_columns(&mut self, declared: Vec<Column<T>>) {
+        if declared == self.declared {
+            return;
+        }
+        let names = |columns: &[Column<T>]| columns.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        let reordered = names(&declared) != names(&self.declared);
+        let previous = |column: &Column<T>| self.columns.iter().position(|c| c.name == column.name);
+
+        let mut columns = declared.clone();
+        for column in &mut columns {
+            let before = self.declared.iter().find(|c| c.name == column.name);
+            if let Some(current) = previous(column).map(|i| &self.columns[i]) {
+                if before.is_some_and(|before| before.width == column.width) {
+                    column.width = current.width;
+                }
+                if before.is_some_and(|before| before.visible == column.visible) {
+                    column.visible = current.visible;
+                }
+            }
+        }
+        if !reordered {
+            columns.sort_by_key(|column| previous(column));
+        }
+
+        let indexes: Vec<Option<usize>> = columns.iter().map(previous).collect();
+        self.filters = indexes
+            .iter()
+            .map(|i| i.and_then(|i| self.filters.get(i).cloned().flatten()))
+            .collect();
+        let order = indexes
+            .iter()
+            .map(|i| i.and_then(|i| self.state.order.get(i).copied()).unwrap_or_default())
+            .collect();
+        let priority = self
+            .state
+            .priority
+            .iter()
+            .filter_map(|p| indexes.iter().position(|i| *i == Some(*p)))
+            .collect();
+        self.state = TableState { order, priority };
+        self.columns = columns;
+        self.declared = declared;
+        self.unsort_hidden();
+    }
+
+    /// Removes the hidden columns from the sort keys, as their sorting could not be undone.
+    fn unsort_hidden(&mut self) {
+        self.state = self.fit_sort(self.state.clone());
+    }
+
+    /// Returns the sort state fitted to the columns, without the sort keys of hidden columns.
+    fn fit_sort(&self, mut state: TableState) -> TableState {
+        state.order.resize(self.columns.len(), TableOrder::default());
+        state.priority.retain(|i| *i < self.columns.len());
+        for (index, column) in self.columns.iter().enumerate() {
+            if !column.visible {
+                state.remove_sort_column(index);
+            }
+        }
+        state
+    }
+
+    /// Returns the part of the state the view of the rows is built from, so that the view is not
+    /// built again when the selection, the expanded rows or the widths of the columns change.
+    fn view_inputs(&self) -> Self {
+        Self {
+            columns: self
+                .columns
+                .iter()
+                .map(|column| Column {
+                    width: None,
+                    ..column.clone()
+                })
+                .collect(),
+            data: self.data.clone(),
+            remote: self.remote,
+            state: self.state.clone(),
+            filters: self.filters.clone(),
+            group_by: self.group_by.clone(),
+            group_pagination: self.group_pagination,
+            tree: self.tree,
+            collapsed_nodes: self.collapsed_nodes.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the modes of the table.
+    fn modes(&self) -> Modes {
+        Modes {
+            orderable: self.orderable,
+            filterable: self.filterable,
+            reorderable: self.reorderable,
+            resizable: self.resizable,
+            remote: self.remote,
+            selection: self.selection,
+            expand: self.expand,
+            tree: self.tree,
+        }
+    }
+
+    /// Returns the sorted columns with their order, by decreasing priority.
+    fn sort_columns(&self) -> Vec<(&Column<T>, TableOrder)> {
+        self.state
+            .sort_keys()
+            .into_iter()
+            .filter_map(|(index, order)| Some((self.columns.get(index)?, order)))
+            .collect()
+    }
+
+    /// Returns the columns with an active filter along with it.
+    fn column_filters(&self) -> Vec<(&Column<T>, &ColumnFilter)> {
+        self.columns
+            .iter()
+            .zip(self.filters.iter())
+            .filter_map(|(column, filter)| Some((column, filter.as_ref()?)))
+            .collect()
+    }
+
+    /// Returns the number of generated columns rendered before the data columns.
+    fn leading_columns(&self) -> usize {
+        usize::from(self.expand != ExpandMode::None)
+            + usize::from(self.selection != SelectionMode::None)
+    }
+
+    /// Returns the number of rendered columns.
+    fn column_count(&self) -> usize {
+        self.leading_columns() + self.visible_columns().count()
+    }
+
+    /// Returns the width of each rendered column, leading columns included.
+    ///
+    /// Frozen columns always get a width, as the offsets of the following frozen columns depend on it.
+    fn column_widths(&self, options: &Options) -> Vec<Option<String>> {
+        let frozen = options.frozen_left > 0 || options.frozen_right > 0;
+        let leading = (0..self.leading_columns()).map(|_| frozen.then(|| LEADING_WIDTH.to_string()));
+        let visible: Vec<&Column<T>> = self.visible_columns().map(|(_, c)| c).collect();
+        let right = visible.len().saturating_sub(options.frozen_right);
+        let columns = visible.iter().enumerate().map(|(position, column)| {
+            let frozen = position < options.frozen_left || position >= right;
+            column
+                .width
+                .or_else(|| frozen.then(|| column.min_width.unwrap_or(FROZEN_WIDTH)))
+                .map(|width| format!("{width}px"))
+        });
+        leading.chain(columns).collect()
+    }
+
+    /// Returns the sticky style of each rendered column, leading columns included, or None for
+    /// the columns that are not frozen.
+    fn frozen_styles(&self, options: &Options) -> Vec<Option<String>> {
+        let widths = self.column_widths(options);
+        let count = widths.len();
+        let left = if options.frozen_left > 0 {
+            (self.leading_columns() + options.frozen_left).min(count)
+        } else {
+            0
+        };
+        let right = count - options.frozen_right.min(count - left);
+        let offset = |widths: &[Option<String>]| {
+            let widths: Vec<&str> = widths.iter().flatten().map(String::as_str).collect();
+            if widths.is_empty() {
+                "0".to_string()
+            } else {
+                format!("calc({})", widths.join(" + "))
+            }
+        };
+        (0..count)
+            .map(|i| {
+                if i < left {
+                    Some(format!("position: sticky; left: {}; z-index: 1;", offset(&widths[..i])))
+                } else if i >= right {
+                    Some(format!("position: sticky; right: {}; z-index: 1;", offset(&widths[i + 1..])))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the visible columns with their index in `columns`.
+    fn visible_columns(&self) -> impl Iterator<Item = (usize, &Column<T>)> {
+        self.columns.iter().enumerate().filter(|(_, column)| column.visible)
+    }
+
+    /// Returns the active column filters as data property and filter pairs.
+    fn active_filters(&self) -> Vec<(String, ColumnFilter)> {
+        self.column_filters()
+            .into_iter()
+            .map(|(column, filter)| (column.property().to_string(), filter.clone()))
+            .collect()
+    }
 }
 
 #[cfg(feature="table")]
@@ -93,18 +578,128 @@ where
     fn reduce(self: std::rc::Rc<Self>, action: Self::Action) -> std::rc::Rc<Self> {
         let mut new = (*self).clone();
         match action {
-            Msg::SortColumn(i) => {
-                use TableOrder::Unordered;
-                for (j, x) in new.state.order.iter_mut().enumerate() {
-                    if j == i {
-                        *x = x.rotate();
-                    } else {
-                        *x = Unordered;
+            Msg::SetData(data) => {
+                new.data = Rc::new(data);
+            }
+            Msg::SetFilter(i, filter) => {
+                if let Some(x) = new.filters.get_mut(i) {
+                    *x = filter;
+                }
+            }
+            Msg::SetState(state) => {
+                new.state = new.fit_sort(state);
+            }
+            Msg::ToggleRow(key) => {
+                match new.selection {
+                    SelectionMode::None => {}
+                    SelectionMode::Single => {
+                        let selected = new.selected.contains(&key);
+                        new.selected.clear();
+                        if !selected {
+                            new.selected.insert(key.clone());
+                        }
+                    }
+                    SelectionMode::Multi => {
+                        if !new.selected.remove(&key) {
+                            new.selected.insert(key.clone());
+                        }
                     }
                 }
+                new.anchor = Some(key);
             }
-            Msg::SetData(data) => {
-                new.data = data;
+            Msg::SetRowsSelected(keys, selected) => {
+                if new.selection == SelectionMode::Multi {
+                    for key in keys {
+                        if selected {
+                            new.selected.insert(key);
+                        } else {
+                            new.selected.remove(&key);
+                        }
+                    }
+                }
+            }
+            Msg::ToggleDetails(key) => match new.expand {
+                ExpandMode::None => {}
+                ExpandMode::Single => {
+                    let expanded = new.expanded.contains(&key);
+                    new.expanded.clear();
+                    if !expanded {
+                        new.expanded.insert(key);
+                    }
+                }
+                ExpandMode::Multiple => {
+                    if !new.expanded.remove(&key) {
+                        new.expanded.insert(key);
+                    }
+                }
+            },
+            Msg::SetRowsExpanded(keys, expanded) => {
+                if new.expand == ExpandMode::Multiple {
+                    for key in keys {
+                        if expanded {
+                            new.expanded.insert(key);
+                        } else {
+                            new.expanded.remove(&key);
+                        }
+                    }
+                }
+            }
+            Msg::SetGrouping(group_by, pagination) => {
+                if new.group_by != group_by {
+                    new.collapsed.clear();
+                }
+                new.group_by = group_by;
+                new.group_pagination = pagination;
+            }
+            Msg::ToggleGroup(path) => {
+                if !new.collapsed.remove(&path) {
+                    new.collapsed.insert(path);
+                }
+            }
+            Msg::ToggleNode(key) => {
+                if !new.collapsed_nodes.remove(&key) {
+                    new.collapsed_nodes.insert(key);
+                }
+            }
+            Msg::ToggleColumn(i) => {
+                if let Some(column) = new.columns.get_mut(i) {
+                    column.visible = !column.visible;
+                }
+                new.unsort_hidden();
+            }
+            Msg::MoveColumn(from, to) => {
+                if from < new.columns.len() && to < new.columns.len() {
+                    let column = new.columns.remove(from);
+                    new.columns.insert(to, column);
+                    let filter = new.filters.remove(from);
+                    new.filters.insert(to, filter);
+                    new.state.move_column(from, to);
+                }
+            }
+            Msg::ResizeColumn(i, width) => {
+                if let Some(column) = new.columns.get_mut(i) {
+                    column.width = Some(width);
+                }
+            }
+            Msg::SetColumns(columns) => {
+                new.set_columns(columns);
+            }
+            Msg::SetModes(modes) => {
+                new.orderable = modes.orderable;
+                new.filterable = modes.filterable;
+                new.reorderable = modes.reorderable;
+                new.resizable = modes.resizable;
+                new.remote = modes.remote;
+                if new.selection != modes.selection {
+                    new.selection = modes.selection;
+                    new.selected.clear();
+                    new.anchor = None;
+                }
+                if new.expand != modes.expand {
+                    new.expand = modes.expand;
+                    new.expanded.clear();
+                }
+                new.tree = modes.tree;
             }
         };
         new.into()
@@ -129,6 +724,48 @@ pub struct Pagination {
     pub page: usize,
 }
 
+/// Virtual scrolling settings.
+///
+/// Rows are expected to have a fixed height.
+#[cfg(feature="table")]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct VirtualScroll {
+    /// Height of a row in pixels.
+    pub row_height: u32,
+    /// Height of the scroll container in pixels.
+    pub height: u32,
+    /// Number of rows rendered above and below the visible ones.
+    pub overscan: usize,
+}
+
+/// Position of the scroll container of a virtually scrolled table.
+#[cfg(feature="table")]
+#[derive(Clone, Copy, Eq, PartialEq, Default)]
+pub struct Viewport {
+    /// Virtual scrolling settings.
+    pub virtual_scroll: Option<VirtualScroll>,
+    /// Index of the first visible row.
+    pub first_row: usize,
+}
+
+/// Callbacks reporting the state of the table to its parent.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Default)]
+pub struct Callbacks {
+    /// Called with the new sort state when the user changes the sorting.
+    pub on_sort: Callback<TableState>,
+    /// Whether the sort state is controlled by the parent, in which case sorting only emits `on_sort`.
+    pub sort_controlled: bool,
+    /// Called with the row key, the data property and the new value when a cell edit is committed.
+    pub on_cell_edit: Callback<(RowKey, String, Value)>,
+    /// Called with the format and the content of an export made from the toolbar.
+    pub on_export: Callback<(ExportFormat, String)>,
+    /// Called with the name and the width of a column when the user resizes it.
+    pub on_column_resize: Callback<(String, u32)>,
+    /// Called with the errors met while reading the data.
+    pub on_error: Callback<error::Error>,
+}
+
 /// Table component.
 ///
 /// # Properties
@@ -136,29 +773,111 @@ pub struct Pagination {
 /// * `columns` - Columns of the table.
 /// * `data` - Table data.
 /// * `orderable` - Whether the table is orderable.
+/// * `filterable` - Whether the filter row is rendered under the header.
+/// * `reorderable` - Whether the columns can be reordered.
+/// * `resizable` - Whether the columns can be resized.
+/// * `on_column_resize` - Called with the name and the width of a column whenever the user resizes it.
 /// * `classes` - Css classes for the table.
 /// * `search` - Search string.
 /// * `options` - Options for the table.
+/// * `limit` - Limit of data displayed for pagination.
+/// * `page` - Page for pagination.
+/// * `on_filtered_count` - Called with the number of rows matching the search.
+/// * `sort` - Sort state controlled by the parent.
+/// * `on_sort` - Called with the new sort state when the user changes the sorting.
+/// * `remote` - Whether the data is sorted, filtered and paginated by the parent.
+/// * `on_query` - Called with the query for the rows to display when the table is remote.
+/// * `total` - Total number of rows matching the query when the table is remote.
+/// * `virtual_scroll` - Renders only the rows in view inside a scroll container when set.
+/// * `selection` - Row selection mode.
+/// * `on_selection_change` - Called with the keys of the selected rows whenever the selection changes.
+/// * `expand` - Detail row expansion mode.
+/// * `on_cell_edit` - Called with the row key, the data property and the new value of an edited cell.
+/// * `export` - Renders a toolbar with export buttons above the table when set.
+/// * `on_export` - Called with the format and the content of every export made from the toolbar.
+/// * `aggregate_scope` - Rows the aggregates of the columns are computed over.
+/// * `group_by` - Data properties the rows are grouped by.
+/// * `group_pagination` - Unit counted by the pagination when the rows are grouped.
+/// * `group_aggregates` - Whether the group header rows render the aggregates of the columns.
+/// * `tree` - Whether the rows form a tree through `TableData::parent_key`.
+/// * `column_chooser` - Renders a column chooser with the given label in the toolbar when set.
+/// * `on_columns_change` - Called with the columns whenever their visibility or their order changes.
+/// * `on_error` - Called with the errors met while reading the data, such as a sort key that
+///   cannot be read. Rows without sort keys come last and cells without Html render
+///   `Options::error_placeholder`.
+/// * `loading` - Whether the data is loading, in which case skeleton rows replace the rows.
+/// * `empty` - Rendered across the columns when no row matches the search and the filters.
+/// * `error` - Rendered across the columns instead of the rows when set, as an error state.
 #[cfg(feature="table")]
 #[function_component(Table)]
 pub fn table<T>(props: &Props<T>) -> Html
 where
     T: TableData + Debug,
 {
-    let data = props.data.clone();
     let columns = props.columns.clone();
     let column_number = props.columns.len();
     let orderable = props.orderable;
+    let filterable = props.filterable;
+    let remote = props.remote;
+    let selection = props.selection;
+    let expand = props.expand;
+    let tree = props.tree;
     let state = use_reducer_eq(|| Data {
+        declared: columns.clone(),
         columns,
-        data: vec![],
+        data: Rc::default(),
         orderable,
+        reorderable: props.reorderable,
+        resizable: props.resizable,
+        filterable,
+        remote,
         state: TableState {
             order: vec![TableOrder::default(); column_number],
+            priority: vec![],
         },
+        filters: vec![None; column_number],
+        selection,
+        selected: BTreeSet::new(),
+        anchor: None,
+        expand,
+        expanded: BTreeSet::new(),
+        group_by: vec![],
+        group_pagination: GroupPagination::default(),
+        collapsed: BTreeSet::new(),
+        tree,
+        collapsed_nodes: BTreeSet::new(),
     });
 
-    state.dispatch(Msg::SetData(data));
+    // Every dispatch copies the state, so only the properties that differ from it are dispatched.
+    let modes = Modes {
+        orderable,
+        filterable,
+        reorderable: props.reorderable,
+        resizable: props.resizable,
+        remote,
+        selection,
+        expand,
+        tree,
+    };
+    let columns_changed = state.declared != props.columns;
+    if columns_changed {
+        state.dispatch(Msg::SetColumns(props.columns.clone()));
+    }
+    if state.modes() != modes {
+        state.dispatch(Msg::SetModes(modes));
+    }
+    if *state.data != props.data {
+        state.dispatch(Msg::SetData(props.data.clone()));
+    }
+    if state.group_by != props.group_by || state.group_pagination != props.group_pagination {
+        state.dispatch(Msg::SetGrouping(props.group_by.clone(), props.group_pagination));
+    }
+    if let Some(sort) = props.sort.clone() {
+        let sort = state.fit_sort(sort);
+        if columns_changed || sort != state.state {
+            state.dispatch(Msg::SetState(sort));
+        }
+    }
 
     let search = Search {
         search: props.search.clone(),
@@ -168,19 +887,317 @@ where
 
     let limit = props.limit;
     let page = props.page;
+    let query = Query {
+        sort: state.sort_keys(),
+        search: props.search.clone(),
+        filters: state.active_filters(),
+        page,
+        limit,
+    };
+    {
+        let on_query = props.on_query.clone();
+        use_effect_with(query, move |query| {
+            if remote {
+                on_query.emit(query.clone());
+            }
+        });
+    }
+
+    let view = use_memo(
+        (state.view_inputs(), search, Pagination { limit, page }, props.total),
+        |(state, search, pagination, total)| body::View::new(state, search, pagination, *total),
+    );
+    {
+        let on_error = props.on_error.clone();
+        use_effect_with(view.errors.clone(), move |errors| {
+            errors.iter().cloned().for_each(|e| on_error.emit(e));
+        });
+    }
+    {
+        let on_filtered_count = props.on_filtered_count.clone();
+        use_effect_with(view.count, move |count| {
+            on_filtered_count.emit(*count);
+        });
+    }
+    {
+        let on_selection_change = props.on_selection_change.clone();
+        use_effect_with(state.selected.clone(), move |selected| {
+            on_selection_change.emit(selected.iter().cloned().collect());
+        });
+    }
+    {
+        let on_columns_change = props.on_columns_change.clone();
+        let layout: Vec<(String, bool)> = state
+            .columns
+            .iter()
+            .map(|c| (c.name.clone(), c.visible))
+            .collect();
+        let columns = state.columns.clone();
+        use_effect_with(layout, move |_| {
+            on_columns_change.emit(columns);
+        });
+    }
+
+    let callbacks = Callbacks {
+        on_sort: props.on_sort.clone(),
+        sort_controlled: props.sort.is_some(),
+        on_cell_edit: props.on_cell_edit.clone(),
+        on_export: props.on_export.clone(),
+        on_column_resize: props.on_column_resize.clone(),
+        on_error: props.on_error.clone(),
+    };
+
+    // An automatic layout only takes the widths of the columns as minimums.
+    let fixed = props.resizable || state.columns.iter().any(|c| c.width.is_some());
+    let table = html! {
+        <table class={classes!(classes)} style={fixed.then_some("table-layout: fixed;")} aria-busy={props.loading.to_string()}>
+            <colgroup>
+                {for state.column_widths(&props.options).into_iter().map(|width| html! {
+                    <col style={width.map(|width| format!("width: {width};"))} />
+                })}
+            </colgroup>
+            <head::TableHead<T> />
+            <body::TableBody<T> group_aggregates={props.group_aggregates} loading={props.loading}
+                skeleton_rows={props.limit.unwrap_or(SKELETON_ROWS)} empty={props.empty.clone()} error={props.error.clone()} />
+            if props.columns.iter().any(|c| c.aggregate.is_some()) {
+                <foot::TableFoot<T> scope={props.aggregate_scope} />
+            }
+        </table>
+    };
 
     html! {
         <ContextProvider<UseReducerHandle<Data<T>>> context={state}>
-            <table class={classes!(classes)}>
-                <ContextProvider<Options> context={options}>
-                    <head::TableHead<T> />
-                </ContextProvider<Options>>
-                <ContextProvider<Search> context={search}>
-                    <ContextProvider<Pagination> context={Pagination { limit, page }}>
-                        <body::TableBody<T> />
-                    </ContextProvider<Pagination>>
-                </ContextProvider<Search>>
-            </table>
+            <ContextProvider<Rc<body::View<T>>> context={view}>
+                <ContextProvider<Callbacks> context={callbacks}>
+                    <ContextProvider<Options> context={options}>
+                        if props.column_chooser.is_some() || props.export.is_some() {
+                            <div class={classes!(props.options.toolbar_classes.clone())}>
+                                if let Some(label) = props.column_chooser.clone() {
+                                    <chooser::ColumnChooser<T> {label} />
+                                }
+                                if let Some(export) = props.export.clone() {
+                                    <export::ExportButtons<T> {export} />
+                                }
+                            </div>
+                        }
+                        if let Some(virtual_scroll) = props.virtual_scroll {
+                            <body::Scroller {virtual_scroll}>
+                                { table }
+                            </body::Scroller>
+                        } else if let Some(max_height) = props.options.max_height.as_deref() {
+                            <div style={format!("max-height: {max_height}; overflow: auto;")}>
+                                { table }
+                            </div>
+                        } else if props.options.frozen_left > 0 || props.options.frozen_right > 0 {
+                            <div style="overflow-x: auto;">
+                                { table }
+                            </div>
+                        } else {
+                            { table }
+                        }
+                    </ContextProvider<Options>>
+                </ContextProvider<Callbacks>>
+            </ContextProvider<Rc<body::View<T>>>>
         </ContextProvider<UseReducerHandle<Data<T>>>>
     }
 }
+
+#[cfg(all(test, feature="table"))]
+pub(crate) mod tests {
+    use super::error::{Error, Result};
+    use super::types::{ColumnBuilder, TableData};
+    use super::*;
+    use serde::Serialize;
+
+    /// Row used by the tests of the table.
+    #[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize)]
+    pub(crate) struct Line {
+        pub(crate) id: u32,
+        pub(crate) parent: Option<u32>,
+        pub(crate) name: String,
+        pub(crate) group: String,
+    }
+
+    impl TableData for Line {
+        fn get_field_as_html(&self, field_name: &str) -> Result<Html> {
+            self.get_field_as_value(field_name)
+                .map(|value| Html::from(format!("{value:?}")))
+        }
+
+        fn get_field_as_value(&self, field_name: &str) -> Result<Value> {
+            match field_name {
+                "id" => Ok(Value::U32(self.id)),
+                "parent" => Ok(Value::Option(self.parent.map(|p| Box::new(Value::U32(p))))),
+                "name" => Ok(Value::String(self.name.clone())),
+                "group" => Ok(Value::String(self.group.clone())),
+                _ => Err(Error::InvalidFieldName(field_name.to_string())),
+            }
+        }
+
+        fn matches_search(&self, needle: Option<String>) -> bool {
+            needle.is_none_or(|needle| self.name.contains(&needle))
+        }
+
+        fn row_key(&self) -> Option<String> {
+            Some(self.id.to_string())
+        }
+
+        fn parent_key(&self) -> Option<String> {
+            self.parent.map(|p| p.to_string())
+        }
+    }
+
+    /// Returns a row of the tests.
+    pub(crate) fn line(id: u32, parent: Option<u32>, name: &str, group: &str) -> Line {
+        Line {
+            id,
+            parent,
+            name: name.to_string(),
+            group: group.to_string(),
+        }
+    }
+
+    /// Returns the rows with their keys.
+    pub(crate) fn keyed(lines: &[Line]) -> Vec<(RowKey, Line)> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| (RowKey::new(index, line), line.clone()))
+            .collect()
+    }
+
+    fn columns() -> Vec<Column<Line>> {
+        ["id", "name", "group"]
+            .into_iter()
+            .map(|name| {
+                ColumnBuilder::new(name)
+                    .renderer(Callback::from(|line: Line| Html::from(line.name)))
+                    .build()
+            })
+            .collect()
+    }
+
+    fn names(data: &Data<Line>) -> Vec<&str> {
+        data.columns.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn query_falls_back_to_the_column_names() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data)
+            .reduce(Msg::SetFilter(1, Some(ColumnFilter::Text("a".to_string()))))
+            .reduce(Msg::SetState(TableState {
+                order: vec![TableOrder::Unordered, TableOrder::Unordered, TableOrder::Descending],
+                priority: vec![2],
+            }));
+        assert_eq!(data.sort_keys(), vec![("group".to_string(), TableOrder::Descending)]);
+        assert_eq!(data.active_filters(), vec![("name".to_string(), ColumnFilter::Text("a".to_string()))]);
+    }
+
+    #[test]
+    fn changing_the_selection_mode_clears_the_selection() {
+        let modes = |selection| Modes {
+            selection,
+            ..Modes::default()
+        };
+        let data = Rc::new(Data::<Line>::default())
+            .reduce(Msg::SetModes(modes(SelectionMode::Multi)))
+            .reduce(Msg::SetRowsSelected(vec![RowKey::Index(0), RowKey::Index(1)], true))
+            .reduce(Msg::SetModes(modes(SelectionMode::Multi)));
+        assert_eq!(data.selected.len(), 2);
+        let data = data.reduce(Msg::SetModes(modes(SelectionMode::Single)));
+        assert!(data.selected.is_empty());
+    }
+
+    #[test]
+    fn set_columns_keeps_the_layout_of_the_user() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data)
+            .reduce(Msg::SetFilter(2, Some(ColumnFilter::Text("a".to_string()))))
+            .reduce(Msg::MoveColumn(2, 0))
+            .reduce(Msg::ResizeColumn(1, 50))
+            .reduce(Msg::ToggleColumn(2))
+            .reduce(Msg::SetColumns(columns()));
+        assert_eq!(names(&data), vec!["group", "id", "name"]);
+        assert_eq!(data.columns[1].width, Some(50));
+        assert!(!data.columns[2].visible);
+        assert_eq!(data.filters[0], Some(ColumnFilter::Text("a".to_string())));
+    }
+
+    #[test]
+    fn set_columns_follows_the_visibility_of_the_properties() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data).reduce(Msg::ToggleColumn(0));
+        assert!(!data.columns[0].visible);
+
+        let mut hidden = columns();
+        hidden[1].visible = false;
+        let data = data.reduce(Msg::SetColumns(hidden));
+        assert_eq!(data.columns.iter().map(|c| c.visible).collect::<Vec<_>>(), vec![false, false, true]);
+        let data = data.reduce(Msg::SetColumns(columns()));
+        assert_eq!(data.columns.iter().map(|c| c.visible).collect::<Vec<_>>(), vec![false, true, true]);
+    }
+
+    #[test]
+    fn set_columns_follows_the_widths_of_the_properties() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data).reduce(Msg::ResizeColumn(0, 50)).reduce(Msg::ResizeColumn(1, 60));
+
+        let mut restored = columns();
+        restored[1].width = Some(120);
+        let data = data.reduce(Msg::SetColumns(restored));
+        assert_eq!(data.columns[0].width, Some(50));
+        assert_eq!(data.columns[1].width, Some(120));
+    }
+
+    #[test]
+    fn selecting_and_resizing_keep_the_view_inputs() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data)
+            .reduce(Msg::SetData(vec![line(1, None, "a", ""), line(2, None, "b", "")]))
+            .reduce(Msg::SetModes(Modes {
+                selection: SelectionMode::Multi,
+                ..Modes::default()
+            }));
+        let inputs = data.view_inputs();
+        let data = data
+            .reduce(Msg::ToggleRow(RowKey::Key("1".to_string())))
+            .reduce(Msg::ResizeColumn(0, 50));
+        assert!(data.view_inputs() == inputs);
+        assert!(data.reduce(Msg::SetFilter(1, Some(ColumnFilter::Text("a".to_string())))).view_inputs() != inputs);
+    }
+
+    #[test]
+    fn hiding_a_column_removes_its_sort_key() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data)
+            .reduce(Msg::SetState(TableState {
+                order: vec![TableOrder::Ascending, TableOrder::Descending, TableOrder::Unordered],
+                priority: vec![1, 0],
+            }))
+            .reduce(Msg::ToggleColumn(1));
+        assert_eq!(data.state.sort_keys(), vec![(0, TableOrder::Ascending)]);
+    }
+
+    #[test]
+    fn set_columns_follows_the_order_of_the_properties() {
+        let mut data = Data::<Line>::default();
+        data.set_columns(columns());
+        let data = Rc::new(data)
+            .reduce(Msg::SetFilter(0, Some(ColumnFilter::Text("1".to_string()))))
+            .reduce(Msg::SetState(TableState {
+                order: vec![TableOrder::Ascending, TableOrder::Unordered, TableOrder::Descending],
+                priority: vec![2, 0],
+            }))
+            .reduce(Msg::SetColumns(columns().into_iter().rev().skip(1).collect()));
+        assert_eq!(names(&data), vec!["name", "id"]);
+        assert_eq!(data.filters, vec![None, Some(ColumnFilter::Text("1".to_string()))]);
+        assert_eq!(data.state.sort_keys(), vec![(1, TableOrder::Ascending)]);
+    }
+}
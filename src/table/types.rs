@@ -2,10 +2,19 @@
 //!
 //! This module contains the types used by the table module.
 use super::error::Result;
+use super::value::{as_f64, as_string, compare, inner, natural_cmp};
 use serde::Serialize;
 use serde_value::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::fmt;
-use yew::Html;
+use std::hash::Hash;
+use web_sys::js_sys;
+use web_sys::wasm_bindgen::JsValue;
+use yew::{Callback, Html};
+
+#[cfg(feature="derive")]
+pub use yew_custom_components_derive::TableData;
 
 /// Trait for table data.
 #[cfg(feature="table")]
@@ -18,12 +27,128 @@ pub trait TableData: 'static + Default + Clone + PartialOrd + Serialize {
 
     /// Returns true if the row matches the search query.
     fn matches_search(&self, needle: Option<String>) -> bool;
+
+    /// Returns a key uniquely identifying the row, used to keep the identity of the row across
+    /// sorts and data updates. When None, the row is identified by its index in the data.
+    fn row_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the key of the parent row, as returned by its `row_key`, when the table is a tree.
+    /// When None, or when the parent is missing, the row is a root.
+    fn parent_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the Html of the detail panel shown below the row when it is expanded.
+    /// When None, the row cannot be expanded.
+    fn render_details(&self) -> Option<Html> {
+        None
+    }
 }
 
-/// A column in a table.
+/// Trait for table data whose fields are named by a type rather than by strings.
+///
+/// Columns built with `ColumnBuilder::field` and groupings built with `field_names` can then
+/// only reference existing fields.
+/// The `TableData` derive implements it with a generated enum.
 #[cfg(feature="table")]
-#[derive(Clone, Eq, PartialEq, Default, Debug)]
-pub struct Column {
+pub trait TableFields: TableData {
+    /// The fields of the data.
+    type Field: TableField;
+
+    /// Returns the names of fields, such as the `group_by` property of the table.
+    fn field_names(fields: &[Self::Field]) -> Vec<String> {
+        fields.iter().map(|field| field.name().to_string()).collect()
+    }
+}
+
+/// Trait for the fields of table data.
+#[cfg(feature="table")]
+pub trait TableField: 'static + Copy + Eq + Hash + fmt::Debug {
+    /// Returns the name under which `TableData` exposes the field.
+    fn name(self) -> &'static str;
+
+    /// Returns all the fields.
+    fn all() -> &'static [Self];
+
+    /// Returns the field exposed under a name, such as the data property given to `on_cell_edit`
+    /// or listed in a `Query`.
+    fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|field| field.name() == name)
+    }
+}
+
+/// Key identifying a row.
+///
+/// The key stays attached to the row when the table is sorted or filtered, and is used as the
+/// key of the rendered row.
+#[cfg(feature="table")]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum RowKey {
+    /// Key returned by `TableData::row_key`.
+    Key(String),
+    /// Index of the row in the `data` property of the table.
+    Index(usize),
+}
+
+#[cfg(feature="table")]
+impl RowKey {
+    /// Returns the key of a row given its index in the `data` property of the table.
+    pub fn new<T: TableData>(index: usize, row: &T) -> Self {
+        row.row_key().map_or(Self::Index(index), Self::Key)
+    }
+
+    /// Returns the key of the rendered row, telling keys from indexes.
+    pub(crate) fn html_key(&self) -> String {
+        match self {
+            Self::Key(key) => format!("k:{key}"),
+            Self::Index(index) => format!("i:{index}"),
+        }
+    }
+}
+
+#[cfg(feature="table")]
+impl fmt::Display for RowKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key}"),
+            Self::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+/// Row selection mode of a table.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum SelectionMode {
+    /// Rows cannot be selected.
+    #[default]
+    None,
+    /// A single row can be selected.
+    Single,
+    /// Multiple rows can be selected.
+    Multi,
+}
+
+/// Detail row expansion mode of a table.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ExpandMode {
+    /// Rows cannot be expanded.
+    #[default]
+    None,
+    /// Only one row can be expanded at a time.
+    Single,
+    /// Any number of rows can be expanded, with an expand all toggle in the header.
+    Multiple,
+}
+
+/// A column in a table of rows of type `T`.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug)]
+#[allow(unpredictable_function_pointer_comparisons)]
+pub struct Column<T> {
     /// The name of the column.
     pub name: String,
     /// The short name of the column.
@@ -34,10 +159,88 @@ pub struct Column {
     pub orderable: bool,
     /// The classes of the column header.
     pub header_classes: Vec<String>,
+    /// The kind of filter offered for the column in the filter row.
+    pub filter: Option<FilterKind>,
+    /// The editor opened on the cells of the column. When None, the cells are read-only.
+    pub editor: Option<Editor>,
+    /// Validation of the edited values, returning the message shown when a value is rejected.
+    pub validate: Option<Callback<Value, std::result::Result<(), String>>>,
+    /// The aggregate rendered for the column in the footer.
+    pub aggregate: Option<Aggregate>,
+    /// Whether the column is rendered.
+    pub visible: bool,
+    /// Width of the column in pixels. Setting it gives the table a fixed layout.
+    pub width: Option<u32>,
+    /// Smallest width in pixels the column can be resized to.
+    pub min_width: Option<u32>,
+    /// Largest width in pixels the column can be resized to.
+    pub max_width: Option<u32>,
+    /// The name of the group of the column, rendered above the consecutive columns of the group.
+    pub group: Option<String>,
+    /// Renders the cells of the column instead of `TableData::get_field_as_html`.
+    pub renderer: Option<Callback<T, Html>>,
+    /// Returns the value of the cells of the column instead of `TableData::get_field_as_value`.
+    pub value: Option<fn(&T) -> Value>,
+    /// How the values of the column are compared when it is sorted.
+    pub sort: SortStrategy,
+    /// Where the empty values of the column go when it is sorted.
+    pub nulls: Nulls,
+}
+
+#[cfg(feature="table")]
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            short_name: None,
+            data_property: None,
+            orderable: false,
+            header_classes: vec![],
+            filter: None,
+            editor: None,
+            validate: None,
+            aggregate: None,
+            visible: true,
+            width: None,
+            min_width: None,
+            max_width: None,
+            group: None,
+            renderer: None,
+            value: None,
+            sort: SortStrategy::default(),
+            nulls: Nulls::default(),
+        }
+    }
 }
 
 #[cfg(feature="table")]
-impl fmt::Display for Column {
+impl<T: TableData> Column<T> {
+    /// Returns the data property of the column, which defaults to its name.
+    pub fn property(&self) -> &str {
+        self.data_property.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Returns the Html of the cell of the column for a row, from the renderer of the column
+    /// when set.
+    pub fn html_of(&self, row: &T) -> Result<Html> {
+        match &self.renderer {
+            Some(renderer) => Ok(renderer.emit(row.clone())),
+            None => row.get_field_as_html(self.property()),
+        }
+    }
+
+    /// Returns the value of the cell of the column for a row, from the value accessor of the
+    /// column when set.
+    pub fn value_of(&self, row: &T) -> Result<Value> {
+        match self.value {
+            Some(value) => Ok(value(row)),
+            None => row.get_field_as_value(self.property()),
+        }
+    }
+}
+
+#[cfg(feature="table")]
+impl<T> fmt::Display for Column<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.short_name.as_ref().unwrap_or(&self.name))
     }
@@ -45,17 +248,36 @@ impl fmt::Display for Column {
 
 /// A builder for a column.
 #[cfg(feature="table")]
-#[derive(Default)]
-pub struct ColumnBuilder {
+pub struct ColumnBuilder<T> {
     name: String,
     short_name: Option<String>,
     data_property: Option<String>,
     orderable: bool,
     header_classes: Vec<String>,
+    filter: Option<FilterKind>,
+    editor: Option<Editor>,
+    validate: Option<Callback<Value, std::result::Result<(), String>>>,
+    aggregate: Option<Aggregate>,
+    visible: bool,
+    width: Option<u32>,
+    min_width: Option<u32>,
+    max_width: Option<u32>,
+    group: Option<String>,
+    renderer: Option<Callback<T, Html>>,
+    value: Option<fn(&T) -> Value>,
+    sort: SortStrategy,
+    nulls: Nulls,
 }
 
 #[cfg(feature="table")]
-impl ColumnBuilder {
+impl<T> Default for ColumnBuilder<T> {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+#[cfg(feature="table")]
+impl<T> ColumnBuilder<T> {
     /// Creates a new column builder.
     ///
     /// # Arguments
@@ -68,18 +290,44 @@ impl ColumnBuilder {
             data_property: None,
             orderable: false,
             header_classes: vec![],
+            filter: None,
+            editor: None,
+            validate: None,
+            aggregate: None,
+            visible: true,
+            width: None,
+            min_width: None,
+            max_width: None,
+            group: None,
+            renderer: None,
+            value: None,
+            sort: SortStrategy::Value,
+            nulls: Nulls::Last,
         }
     }
 
     /// Builds the column.
     #[allow(clippy::missing_const_for_fn)]
-    pub fn build(self) -> Column {
+    pub fn build(self) -> Column<T> {
         Column {
             name: self.name,
             short_name: self.short_name,
             data_property: self.data_property,
             orderable: self.orderable,
             header_classes: self.header_classes,
+            filter: self.filter,
+            editor: self.editor,
+            validate: self.validate,
+            aggregate: self.aggregate,
+            visible: self.visible,
+            width: self.width,
+            min_width: self.min_width,
+            max_width: self.max_width,
+            group: self.group,
+            renderer: self.renderer,
+            value: self.value,
+            sort: self.sort,
+            nulls: self.nulls,
         }
     }
 
@@ -106,13 +354,466 @@ impl ColumnBuilder {
         self.header_classes.push(class.to_string());
         self
     }
+
+    /// Sets the kind of filter offered for the column.
+    pub fn filter(mut self, filter: FilterKind) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sets the editor opened on the cells of the column.
+    pub fn editor(mut self, editor: Editor) -> Self {
+        self.editor = Some(editor);
+        self
+    }
+
+    /// Sets the validation of the edited values.
+    pub fn validate(mut self, validate: Callback<Value, std::result::Result<(), String>>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    /// Sets the aggregate rendered for the column in the footer.
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
+    /// Sets whether the column is rendered.
+    pub const fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Sets the width of the column in pixels.
+    pub const fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Sets the smallest width in pixels the column can be resized to.
+    pub const fn min_width(mut self, min_width: u32) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    /// Sets the largest width in pixels the column can be resized to.
+    pub const fn max_width(mut self, max_width: u32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Sets the name of the group of the column.
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Sets the data property of the column from a field of the data.
+    pub fn field(mut self, field: T::Field) -> Self
+    where
+        T: TableFields,
+    {
+        self.data_property = Some(field.name().to_string());
+        self
+    }
+
+    /// Sets the renderer of the cells of the column.
+    pub fn renderer(mut self, renderer: Callback<T, Html>) -> Self {
+        self.renderer = Some(renderer);
+        self
+    }
+
+    /// Sets the value accessor of the cells of the column, used to sort, filter, aggregate,
+    /// export and edit them.
+    pub fn value(mut self, value: fn(&T) -> Value) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Sets how the values of the column are compared when it is sorted.
+    pub fn sort(mut self, sort: SortStrategy) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Sets where the empty values of the column go when it is sorted.
+    pub const fn nulls(mut self, nulls: Nulls) -> Self {
+        self.nulls = nulls;
+        self
+    }
+}
+
+/// A group of columns sharing a header above their own.
+///
+/// # Example
+///
+/// ```rust
+/// use yew_custom_components::table::types::{Column, ColumnBuilder, ColumnGroup};
+///
+/// let columns: Vec<Column<()>> = [
+///     vec![ColumnBuilder::new("name").build()],
+///     ColumnGroup::new("Q1", vec![
+///         ColumnBuilder::new("jan").build(),
+///         ColumnBuilder::new("feb").build(),
+///         ColumnBuilder::new("mar").build(),
+///     ])
+///     .into_columns(),
+/// ]
+/// .concat();
+/// assert_eq!(columns[2].group.as_deref(), Some("Q1"));
+/// ```
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnGroup<T> {
+    /// The name of the group.
+    pub name: String,
+    /// The columns of the group.
+    pub children: Vec<Column<T>>,
+}
+
+#[cfg(feature="table")]
+impl<T> ColumnGroup<T> {
+    /// Creates a new column group.
+    pub fn new(name: &str, children: Vec<Column<T>>) -> Self {
+        Self {
+            name: name.to_string(),
+            children,
+        }
+    }
+
+    /// Returns the columns of the group, tagged with its name, to be given to the table.
+    pub fn into_columns(self) -> Vec<Column<T>> {
+        let name = self.name;
+        self.children
+            .into_iter()
+            .map(|column| Column {
+                group: Some(name.clone()),
+                ..column
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature="table")]
+impl<T> From<ColumnGroup<T>> for Vec<Column<T>> {
+    fn from(group: ColumnGroup<T>) -> Self {
+        group.into_columns()
+    }
+}
+
+/// Editor of the cells of a column.
+///
+/// The editor opens on a double click or on Enter, commits on Enter or when it loses the focus,
+/// and is cancelled on Escape.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug)]
+pub enum Editor {
+    /// Text input, committing a string.
+    Text,
+    /// Number input, committing a float.
+    Number,
+    /// Select with the given options, committing a string.
+    Select(Vec<String>),
+    /// Checkbox, committing a boolean.
+    Checkbox,
+    /// Custom editor rendered from the current value of the cell.
+    Custom(Callback<EditorContext, Html>),
+}
+
+/// Context given to a custom editor.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct EditorContext {
+    /// Current value of the cell.
+    pub value: Value,
+    /// Commits the edited value, once validated.
+    pub on_commit: Callback<Value>,
+    /// Cancels the edit.
+    pub on_cancel: Callback<()>,
+}
+
+/// Aggregate of the values of a column.
+///
+/// Aggregates are computed from the values returned by `Column::value_of`.
+/// Empty values are ignored, as are non-numeric values for the numeric aggregates.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug)]
+pub enum Aggregate {
+    /// Sum of the numeric values.
+    Sum,
+    /// Average of the numeric values.
+    Avg,
+    /// Smallest value.
+    Min,
+    /// Largest value.
+    Max,
+    /// Number of values.
+    Count,
+    /// Number of distinct values.
+    DistinctCount,
+    /// Custom aggregate rendered from the values.
+    Custom(Callback<Vec<Value>, Html>),
+}
+
+#[cfg(feature="table")]
+impl Aggregate {
+    /// Returns the Html of the aggregate of the values.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(&self, values: &[Value]) -> Html {
+        let present = values.iter().filter_map(inner);
+        let numbers = values.iter().filter_map(as_f64);
+        let text = match self {
+            Self::Sum => Some(numbers.sum::<f64>().to_string()),
+            Self::Avg => {
+                let numbers: Vec<f64> = numbers.collect();
+                (!numbers.is_empty())
+                    .then(|| (numbers.iter().sum::<f64>() / numbers.len() as f64).to_string())
+            }
+            Self::Min => present.min_by(|a, b| compare(a, b)).and_then(as_string),
+            Self::Max => present.max_by(|a, b| compare(a, b)).and_then(as_string),
+            Self::Count => Some(present.count().to_string()),
+            Self::DistinctCount => Some(present.collect::<BTreeSet<_>>().len().to_string()),
+            Self::Custom(render) => return render.emit(values.to_vec()),
+        };
+        Html::from(text.unwrap_or_default())
+    }
+}
+
+/// Rows the footer aggregates are computed over.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum AggregateScope {
+    /// Every row matching the search and the filters.
+    #[default]
+    Filtered,
+    /// Only the rows of the current page.
+    Page,
+}
+
+/// Unit counted by the pagination of a grouped table.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum GroupPagination {
+    /// Pages hold `limit` rows, with the headers of their groups.
+    #[default]
+    Rows,
+    /// Pages hold `limit` top-level groups with all their rows.
+    Groups,
+}
+
+/// Kind of filter offered for a column.
+#[cfg(feature="table")]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum FilterKind {
+    /// Case insensitive text contains filter.
+    Text,
+    /// Numeric range filter.
+    NumberRange,
+    /// Select filter with the given options.
+    Select(Vec<String>),
+    /// Boolean filter.
+    Boolean,
+    /// Date range filter on ISO 8601 dates.
+    DateRange,
+}
+
+/// Value of a column filter.
+///
+/// Filters are evaluated against the value returned by `Column::value_of`.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug)]
+pub enum ColumnFilter {
+    /// Value contains the text, case insensitively.
+    Text(String),
+    /// Numeric value lies within the inclusive bounds.
+    NumberRange {
+        /// Lower bound.
+        min: Option<f64>,
+        /// Upper bound.
+        max: Option<f64>,
+    },
+    /// Value equals the selected option.
+    Select(String),
+    /// Boolean value equals the given one.
+    Boolean(bool),
+    /// Date lies within the inclusive bounds, compared as `YYYY-MM-DD`.
+    DateRange {
+        /// Lower bound.
+        from: Option<String>,
+        /// Upper bound.
+        to: Option<String>,
+    },
+}
+
+#[cfg(feature="table")]
+impl ColumnFilter {
+    /// Returns true if the value passes the filter.
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            Self::Text(needle) => as_string(value)
+                .is_some_and(|v| v.to_lowercase().contains(&needle.to_lowercase())),
+            Self::NumberRange { min, max } => as_f64(value).is_some_and(|v| {
+                min.is_none_or(|min| v >= min) && max.is_none_or(|max| v <= max)
+            }),
+            Self::Select(option) => as_string(value).is_some_and(|v| v == *option),
+            Self::Boolean(expected) => as_string(value).is_some_and(|v| v == expected.to_string()),
+            Self::DateRange { from, to } => as_string(value).is_some_and(|v| {
+                let date = v.get(..10).unwrap_or(&v);
+                from.as_ref().is_none_or(|from| date >= from.as_str())
+                    && to.as_ref().is_none_or(|to| date <= to.as_str())
+            }),
+        }
+    }
+}
+
+/// How the values of a column are compared when it is sorted.
+///
+/// Empty values, `Value::Unit` and `Value::Option(None)`, are never given to the strategy: they
+/// are placed according to the `Nulls` of the column. Options and newtypes are compared by their
+/// content. Strategies working on text compare the other values as `SortStrategy::Value` does.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_value::Value;
+/// use std::cmp::Ordering;
+/// use yew_custom_components::table::types::{Nulls, SortStrategy, TableOrder};
+///
+/// let (a, b) = (Value::String("item2".to_string()), Value::String("item10".to_string()));
+/// assert_eq!(SortStrategy::Value.compare(&a, &b), Ordering::Greater);
+/// assert_eq!(SortStrategy::Natural.compare(&a, &b), Ordering::Less);
+/// assert_eq!(SortStrategy::Value.compare(&Value::U8(2), &Value::I64(10)), Ordering::Less);
+///
+/// let empty = Value::Option(None);
+/// assert_eq!(Nulls::Last.compare(&SortStrategy::Value, TableOrder::Descending, &empty, &a), Ordering::Greater);
+/// ```
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum SortStrategy {
+    /// Numbers are compared by magnitude whatever their type, strings byte by byte, and other
+    /// values by their `Value` ordering.
+    #[default]
+    Value,
+    /// Strings are compared with their runs of digits compared by numeric value, so that
+    /// "item2" comes before "item10".
+    Natural,
+    /// Strings are compared regardless of their case.
+    CaseInsensitive,
+    /// Strings are compared by the collation of the locale of the browser.
+    Locale,
+    /// Values are compared by the callback.
+    Custom(Callback<(Value, Value), Ordering>),
+}
+
+#[cfg(feature="table")]
+impl SortStrategy {
+    /// Compares two non-empty values in ascending order.
+    pub fn compare(&self, a: &Value, b: &Value) -> Ordering {
+        self.compare_with(a, b, None)
+    }
+
+    /// Compares two non-empty values in ascending order, with the collator built for the sort
+    /// when the strategy is `Locale`.
+    pub(crate) fn compare_with(&self, a: &Value, b: &Value, collator: Option<&Collator>) -> Ordering {
+        let text = || Some((as_text(a)?, as_text(b)?));
+        match self {
+            Self::Value => compare(a, b),
+            Self::Natural => text().map_or_else(|| compare(a, b), |(a, b)| natural_cmp(a, b)),
+            Self::CaseInsensitive => text().map_or_else(
+                || compare(a, b),
+                |(a, b)| a.to_lowercase().cmp(&b.to_lowercase()),
+            ),
+            Self::Locale => text().map_or_else(
+                || compare(a, b),
+                |(a, b)| match collator {
+                    Some(collator) => collator.compare(a, b),
+                    None => Collator::new().compare(a, b),
+                },
+            ),
+            Self::Custom(compare) => compare.emit((a.clone(), b.clone())),
+        }
+    }
+}
+
+/// Collator of the locale of the browser, built once per sort as building it is costly.
+#[cfg(feature="table")]
+pub(crate) struct Collator(js_sys::Function);
+
+#[cfg(feature="table")]
+impl Collator {
+    /// Builds the collator of the locale of the browser.
+    pub(crate) fn new() -> Self {
+        Self(js_sys::Intl::Collator::new(&js_sys::Array::new(), &js_sys::Object::new()).compare())
+    }
+
+    /// Compares two strings by the collation of the locale.
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.0
+            .call2(&JsValue::NULL, &JsValue::from_str(a), &JsValue::from_str(b))
+            .ok()
+            .and_then(|ordering| ordering.as_f64())
+            .and_then(|ordering| ordering.partial_cmp(&0.0))
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Returns the string of a text value.
+#[cfg(feature="table")]
+fn as_text(value: &Value) -> Option<&str> {
+    match inner(value)? {
+        Value::String(v) => Some(v),
+        _ => None,
+    }
+}
+
+/// Where the empty values of a column go when it is sorted, whatever the order.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Nulls {
+    /// Empty values come first.
+    First,
+    /// Empty values come last.
+    #[default]
+    Last,
+}
+
+#[cfg(feature="table")]
+impl Nulls {
+    /// Compares two values of a column sorted in the given order.
+    pub fn compare(self, strategy: &SortStrategy, order: TableOrder, a: &Value, b: &Value) -> Ordering {
+        self.compare_with(strategy, order, a, b, None)
+    }
+
+    /// Compares two values of a column sorted in the given order, with the collator built for
+    /// the sort.
+    pub(crate) fn compare_with(
+        self,
+        strategy: &SortStrategy,
+        order: TableOrder,
+        a: &Value,
+        b: &Value,
+        collator: Option<&Collator>,
+    ) -> Ordering {
+        let empty = |v: &Value| inner(v).is_none();
+        match (empty(a), empty(b)) {
+            (true, true) => Ordering::Equal,
+            (true, false) if self == Self::First => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, true) if self == Self::First => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) if order == TableOrder::Descending => strategy.compare_with(b, a, collator),
+            (false, false) => strategy.compare_with(a, b, collator),
+        }
+    }
 }
 
 /// Order of a column
 #[cfg(feature="table")]
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub enum TableOrder {
     /// The column is unordered.
+    #[default]
     Unordered = 0,
     /// The column is ordered in ascending order.
     Ascending,
@@ -120,13 +821,6 @@ pub enum TableOrder {
     Descending,
 }
 
-#[cfg(feature="table")]
-impl Default for TableOrder {
-    fn default() -> Self {
-        Self::Unordered
-    }
-}
-
 #[cfg(feature="table")]
 impl TableOrder {
 
@@ -149,4 +843,298 @@ impl TableOrder {
 pub struct TableState {
     /// The order of the columns.
     pub order: Vec<TableOrder>,
+    /// Indices of the ordered columns, from the highest sorting priority to the lowest.
+    pub priority: Vec<usize>,
+}
+
+#[cfg(feature="table")]
+impl TableState {
+    /// Sorts the table by a single column.
+    ///
+    /// Rotates the order of the column and resets every other column to unordered.
+    pub fn sort_column(&mut self, index: usize) {
+        for (j, x) in self.order.iter_mut().enumerate() {
+            if j == index {
+                *x = x.rotate();
+            } else {
+                *x = TableOrder::Unordered;
+            }
+        }
+        self.priority.clear();
+        if self.order.get(index).is_some_and(|o| *o != TableOrder::Unordered) {
+            self.priority.push(index);
+        }
+    }
+
+    /// Adds a column to the sort keys, keeping the other sorted columns.
+    ///
+    /// Rotates the order of the column. A newly sorted column gets the lowest priority,
+    /// a column rotated back to unordered is removed from the sort keys.
+    pub fn add_sort_column(&mut self, index: usize) {
+        let Some(order) = self.order.get_mut(index) else {
+            return;
+        };
+        *order = order.rotate();
+        if *order == TableOrder::Unordered {
+            self.priority.retain(|i| *i != index);
+        } else if !self.priority.contains(&index) {
+            self.priority.push(index);
+        }
+    }
+
+    /// Removes a column from the sort keys.
+    pub fn remove_sort_column(&mut self, index: usize) {
+        if let Some(order) = self.order.get_mut(index) {
+            *order = TableOrder::Unordered;
+        }
+        self.priority.retain(|i| *i != index);
+    }
+
+    /// Returns the sort keys as column index and order pairs, from the highest priority to the lowest.
+    ///
+    /// Ordered columns missing from the priority list are appended in column order.
+    pub fn sort_keys(&self) -> Vec<(usize, TableOrder)> {
+        let mut indexes: Vec<usize> = self
+            .priority
+            .iter()
+            .copied()
+            .filter(|i| self.order.get(*i).is_some_and(|o| *o != TableOrder::Unordered))
+            .collect();
+        for (i, o) in self.order.iter().enumerate() {
+            if *o != TableOrder::Unordered && !indexes.contains(&i) {
+                indexes.push(i);
+            }
+        }
+        indexes.into_iter().map(|i| (i, self.order[i])).collect()
+    }
+
+    /// Moves the order of a column to another index, shifting the columns in between, so that
+    /// the sorting follows the column.
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        if from >= self.order.len() || to >= self.order.len() {
+            return;
+        }
+        let order = self.order.remove(from);
+        self.order.insert(to, order);
+        for index in &mut self.priority {
+            *index = moved_index(*index, from, to);
+        }
+    }
+
+    /// Returns the 1-based sorting priority of a column, or None when the column is unordered.
+    pub fn priority_of(&self, index: usize) -> Option<usize> {
+        self.sort_keys()
+            .iter()
+            .position(|(i, _)| *i == index)
+            .map(|p| p + 1)
+    }
+}
+
+/// Returns the new index of the item at `index` once the item at `from` is moved to `to`.
+#[cfg(feature="table")]
+pub(crate) const fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < to && index > from && index <= to {
+        index - 1
+    } else if from > to && index >= to && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Query describing the rows a remote table should display.
+#[cfg(feature="table")]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Query {
+    /// Sort keys as data property and order pairs, from the highest priority to the lowest.
+    pub sort: Vec<(String, TableOrder)>,
+    /// Search string.
+    pub search: Option<String>,
+    /// Column filters as data property and filter pairs.
+    pub filters: Vec<(String, ColumnFilter)>,
+    /// Page for pagination.
+    pub page: usize,
+    /// Limit of data displayed for pagination.
+    pub limit: Option<usize>,
+}
+
+/// Format of an export of the table.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ExportFormat {
+    /// Comma separated values, with quotes escaped as in RFC 4180.
+    Csv,
+    /// Tab separated values.
+    Tsv,
+    /// Array of objects keyed by the data properties of the columns, or by their names when
+    /// several columns share a property.
+    Json,
+}
+
+#[cfg(feature="table")]
+impl ExportFormat {
+    /// Returns the file extension of the format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Json => "json",
+        }
+    }
+
+    /// Returns the MIME type of the format.
+    pub const fn mime_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Tsv => "text/tab-separated-values",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+#[cfg(feature="table")]
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.extension().to_uppercase())
+    }
+}
+
+/// Rows included in an export of the table.
+#[cfg(feature="table")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ExportScope {
+    /// Every row matching the search and the filters.
+    #[default]
+    All,
+    /// Only the rows of the current page.
+    Page,
+}
+
+/// Export toolbar of the table.
+#[cfg(feature="table")]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Export {
+    /// Formats offered, one button each.
+    pub formats: Vec<ExportFormat>,
+    /// Rows included in the export.
+    pub scope: ExportScope,
+    /// Name of the downloaded file, without its extension.
+    pub file_name: String,
+}
+
+#[cfg(feature="table")]
+impl Default for Export {
+    fn default() -> Self {
+        Self {
+            formats: vec![ExportFormat::Csv, ExportFormat::Json],
+            scope: ExportScope::All,
+            file_name: "table".to_string(),
+        }
+    }
+}
+
+#[cfg(all(test, feature="table"))]
+mod tests {
+    use super::TableOrder::{Ascending, Descending, Unordered};
+    use super::*;
+
+    fn state(columns: usize) -> TableState {
+        TableState {
+            order: vec![Unordered; columns],
+            priority: vec![],
+        }
+    }
+
+    #[test]
+    fn min_and_max_compare_numbers_of_any_type() {
+        let values = vec![Value::I64(-3), Value::F64(2.5), Value::U64(7), Value::Unit, Value::I64(5)];
+        assert_eq!(Aggregate::Min.render(&values), Html::from("-3"));
+        assert_eq!(Aggregate::Max.render(&values), Html::from("7"));
+    }
+
+    fn text(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+
+    #[test]
+    fn sort_strategies_compare_strings() {
+        assert_eq!(SortStrategy::Value.compare(&text("item2"), &text("item10")), Ordering::Greater);
+        assert_eq!(SortStrategy::Natural.compare(&text("item2"), &text("item10")), Ordering::Less);
+        assert_eq!(SortStrategy::Value.compare(&text("b"), &text("A")), Ordering::Greater);
+        assert_eq!(SortStrategy::CaseInsensitive.compare(&text("b"), &text("A")), Ordering::Greater);
+        assert_eq!(SortStrategy::CaseInsensitive.compare(&text("a"), &text("B")), Ordering::Less);
+        assert_eq!(SortStrategy::Natural.compare(&Value::U8(3), &Value::I64(20)), Ordering::Less);
+    }
+
+    #[test]
+    fn nulls_keep_their_place_whatever_the_order() {
+        let (empty, value) = (Value::Option(None), Value::I32(1));
+        for order in [Ascending, Descending] {
+            assert_eq!(Nulls::Last.compare(&SortStrategy::Value, order, &empty, &value), Ordering::Greater);
+            assert_eq!(Nulls::First.compare(&SortStrategy::Value, order, &empty, &value), Ordering::Less);
+            assert_eq!(Nulls::First.compare(&SortStrategy::Value, order, &Value::Unit, &empty), Ordering::Equal);
+        }
+        assert_eq!(Nulls::Last.compare(&SortStrategy::Value, Descending, &Value::I32(2), &value), Ordering::Less);
+    }
+
+    #[test]
+    fn sort_column_keeps_a_single_key() {
+        let mut state = state(3);
+        state.add_sort_column(0);
+        state.sort_column(2);
+        assert_eq!(state.sort_keys(), vec![(2, Ascending)]);
+        assert_eq!(state.order, vec![Unordered, Unordered, Ascending]);
+
+        state.sort_column(2);
+        state.sort_column(2);
+        assert!(state.sort_keys().is_empty());
+        assert_eq!(state.priority_of(2), None);
+    }
+
+    #[test]
+    fn add_sort_column_appends_with_lowest_priority() {
+        let mut state = state(3);
+        state.add_sort_column(2);
+        state.add_sort_column(0);
+        state.add_sort_column(2);
+        assert_eq!(state.sort_keys(), vec![(2, Descending), (0, Ascending)]);
+        assert_eq!(state.priority_of(2), Some(1));
+        assert_eq!(state.priority_of(0), Some(2));
+        assert_eq!(state.priority_of(1), None);
+    }
+
+    #[test]
+    fn priorities_are_renumbered_when_a_key_is_removed() {
+        let mut state = state(3);
+        state.add_sort_column(1);
+        state.add_sort_column(0);
+        state.add_sort_column(2);
+        state.add_sort_column(1);
+        state.add_sort_column(1);
+        assert_eq!(state.sort_keys(), vec![(0, Ascending), (2, Ascending)]);
+        assert_eq!(state.priority_of(0), Some(1));
+        assert_eq!(state.priority_of(2), Some(2));
+        assert_eq!(state.priority_of(1), None);
+    }
+
+    #[test]
+    fn remove_sort_column_keeps_the_other_keys() {
+        let mut state = state(3);
+        state.add_sort_column(1);
+        state.add_sort_column(0);
+        state.remove_sort_column(1);
+        assert_eq!(state.sort_keys(), vec![(0, Ascending)]);
+        assert_eq!(state.priority_of(0), Some(1));
+    }
+
+    #[test]
+    fn ordered_columns_missing_from_the_priority_come_last() {
+        let state = TableState {
+            order: vec![Descending, Unordered, Ascending],
+            priority: vec![2],
+        };
+        assert_eq!(state.sort_keys(), vec![(2, Ascending), (0, Descending)]);
+    }
 }
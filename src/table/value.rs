@@ -0,0 +1,120 @@
+//! Helpers for reading the values returned by `TableData::get_field_as_value`.
+
+use serde_value::Value;
+use std::cmp::Ordering;
+
+/// Returns the inner value of options and newtypes, or None for empty values.
+#[cfg(feature="table")]
+pub(crate) fn inner(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Unit | Value::Option(None) => None,
+        Value::Option(Some(v)) | Value::Newtype(v) => inner(v),
+        v => Some(v),
+    }
+}
+
+/// Returns the text representation of a scalar value.
+#[cfg(feature="table")]
+pub(crate) fn as_string(value: &Value) -> Option<String> {
+    match inner(value)? {
+        Value::Bool(v) => Some(v.to_string()),
+        Value::U8(v) => Some(v.to_string()),
+        Value::U16(v) => Some(v.to_string()),
+        Value::U32(v) => Some(v.to_string()),
+        Value::U64(v) => Some(v.to_string()),
+        Value::I8(v) => Some(v.to_string()),
+        Value::I16(v) => Some(v.to_string()),
+        Value::I32(v) => Some(v.to_string()),
+        Value::I64(v) => Some(v.to_string()),
+        Value::F32(v) => Some(v.to_string()),
+        Value::F64(v) => Some(v.to_string()),
+        Value::Char(v) => Some(v.to_string()),
+        Value::String(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the numeric representation of a value.
+#[cfg(feature="table")]
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn as_f64(value: &Value) -> Option<f64> {
+    match inner(value)? {
+        Value::U8(v) => Some(f64::from(*v)),
+        Value::U16(v) => Some(f64::from(*v)),
+        Value::U32(v) => Some(f64::from(*v)),
+        Value::U64(v) => Some(*v as f64),
+        Value::I8(v) => Some(f64::from(*v)),
+        Value::I16(v) => Some(f64::from(*v)),
+        Value::I32(v) => Some(f64::from(*v)),
+        Value::I64(v) => Some(*v as f64),
+        Value::F32(v) => Some(f64::from(*v)),
+        Value::F64(v) => Some(*v),
+        Value::String(v) => v.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Compares two non-empty values, numbers by magnitude whatever their type and other values by
+/// their `Value` ordering.
+#[cfg(feature="table")]
+pub(crate) fn compare(a: &Value, b: &Value) -> Ordering {
+    let (a, b) = (inner(a).unwrap_or(a), inner(b).unwrap_or(b));
+    let number = |v: &Value| (!matches!(v, Value::String(_))).then(|| as_f64(v)).flatten();
+    match (number(a), number(b)) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        _ => a.cmp(b),
+    }
+}
+
+/// Compares two strings with their runs of digits compared by numeric value, so that "item2"
+/// comes before "item10".
+#[cfg(feature="table")]
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if x.is_ascii_digit() && y.is_ascii_digit() {
+            let ((x, rest_a), (y, rest_b)) = (split_digits(a), split_digits(b));
+            let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+            let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(y));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (rest_a, rest_b);
+        } else if x != y {
+            return x.cmp(&y);
+        } else {
+            (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+        }
+    }
+}
+
+/// Splits a string after its leading run of digits.
+#[cfg(feature="table")]
+fn split_digits(s: &str) -> (&str, &str) {
+    s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()))
+}
+
+#[cfg(all(test, feature="table"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_cmp_compares_runs_of_digits_by_value() {
+        assert_eq!(natural_cmp("item2", "item10"), Ordering::Less);
+        assert_eq!(natural_cmp("item10", "item9"), Ordering::Greater);
+        assert_eq!(natural_cmp("item02", "item2"), Ordering::Equal);
+        assert_eq!(natural_cmp("a10b2", "a10b10"), Ordering::Less);
+        assert_eq!(natural_cmp("b1", "a2"), Ordering::Greater);
+        assert_eq!(natural_cmp("item", "item1"), Ordering::Less);
+    }
+
+    #[test]
+    fn compare_orders_numbers_by_magnitude() {
+        assert_eq!(compare(&Value::U64(10), &Value::I8(-2)), Ordering::Greater);
+        assert_eq!(compare(&Value::F32(1.5), &Value::I64(2)), Ordering::Less);
+        assert_eq!(compare(&Value::String("10".to_string()), &Value::String("9".to_string())), Ordering::Less);
+    }
+}